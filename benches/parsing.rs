@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use filmapik_api::scraper::parser;
+use filmapik_api::scraper::selectors::{self, Selectors};
+
+const LISTING_HTML: &str = include_str!("../tests/fixtures/listing.html");
+const DETAIL_HTML: &str = include_str!("../tests/fixtures/detail.html");
+
+const DETAIL_URL: &str = "http://194.102.105.201/paayum-oli-nee-yenakku-2023/";
+
+fn bench_listing(c: &mut Criterion) {
+    selectors::init();
+
+    let mut group = c.benchmark_group("listing");
+    group.throughput(Throughput::Bytes(LISTING_HTML.len() as u64));
+
+    group.bench_function("precompiled", |b| {
+        b.iter(|| parser::parse_movie_list(black_box(LISTING_HTML)))
+    });
+
+    // Perilaku lama: selector dikompilasi ulang setiap kali halaman di-parse
+    group.bench_function("compile_per_call", |b| {
+        b.iter(|| {
+            let selectors = Selectors::load_default().unwrap();
+            parser::parse_movie_list_with(&selectors, black_box(LISTING_HTML))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_detail(c: &mut Criterion) {
+    selectors::init();
+
+    let mut group = c.benchmark_group("detail");
+    group.throughput(Throughput::Bytes(DETAIL_HTML.len() as u64));
+
+    group.bench_function("precompiled", |b| {
+        b.iter(|| parser::parse_movie_detail(black_box(DETAIL_HTML), "paayum-oli-nee-yenakku-2023", DETAIL_URL))
+    });
+
+    group.bench_function("compile_per_call", |b| {
+        b.iter(|| {
+            let selectors = Selectors::load_default().unwrap();
+            parser::parse_movie_detail_with(&selectors, black_box(DETAIL_HTML), "paayum-oli-nee-yenakku-2023", DETAIL_URL)
+        })
+    });
+
+    group.finish();
+}
+
+// Biaya parse konfigurasi dan kompilasi seluruh selector; versi lama membayar biaya ini per halaman (dan sebagian per item listing)
+fn bench_selector_compile(c: &mut Criterion) {
+    c.bench_function("selectors/compile", |b| b.iter(Selectors::load_default));
+}
+
+criterion_group!(benches, bench_listing, bench_detail, bench_selector_compile);
+criterion_main!(benches);
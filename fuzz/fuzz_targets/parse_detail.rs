@@ -0,0 +1,28 @@
+// Fuzz target parser halaman detail film
+#![no_main]
+
+use filmapik_api::scraper::parser;
+use libfuzzer_sys::fuzz_target;
+
+// Jumlah field detail yang dihitung pada skor kelengkapan (lihat parser.rs)
+const DETAIL_EXPECTED_FIELDS: f32 = 15.0;
+
+fuzz_target!(|html: &str| {
+    let detail = parser::parse_movie_detail(html, "film", "http://localhost/film/");
+    let warnings = detail.parse_warnings.expect("parser selalu mengisi parse_warnings");
+
+    let mut fields: Vec<_> = warnings.iter().map(|w| w.field.as_str()).collect();
+    fields.sort_unstable();
+    fields.dedup();
+    assert_eq!(fields.len(), warnings.len(), "field warning duplikat");
+
+    assert!((0.0..=1.0).contains(&detail.completeness));
+    assert_eq!(detail.completeness, 1.0 - warnings.len() as f32 / DETAIL_EXPECTED_FIELDS);
+    assert!(!detail.title.is_empty() || fields.contains(&"title"));
+    assert_eq!(detail.views.is_none(), fields.contains(&"views"));
+    assert_eq!(detail.year.is_none(), fields.contains(&"year"));
+
+    for movie in &detail.related_movies {
+        assert!(!movie.id.contains('/'));
+    }
+});
@@ -0,0 +1,22 @@
+// Fuzz target parser halaman listing (dipakai oleh /api/movie/latest, genre, negara, dan pencarian)
+#![no_main]
+
+use filmapik_api::scraper::parser;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|html: &str| {
+    let listing = parser::parse_movie_list(html);
+
+    for movie in &listing.movies {
+        // ID adalah segmen path terakhir dari URL film
+        assert!(!movie.id.contains('/'));
+        assert!(movie.url.trim_end_matches('/').ends_with(&movie.id));
+        assert_eq!(movie.title, movie.title.trim());
+        assert_eq!(movie.year, parser::year_from_title(&movie.title));
+    }
+
+    // Parse harus deterministik
+    let again = parser::parse_movie_list(html);
+    assert_eq!(again.movies.len(), listing.movies.len());
+    assert_eq!(again.has_container, listing.has_container);
+});
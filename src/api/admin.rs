@@ -0,0 +1,121 @@
+use crate::api::params::SnapshotId;
+use crate::errors::AppError;
+use crate::scraper::selectors::{self, SelectorsInfo};
+use crate::scraper::snapshot::{self, SnapshotMeta};
+use crate::scraper::FilmapikScraper;
+use actix_web::{get, http, post, web, HttpResponse, Responder};
+use log::{error, info};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct SelectorsInfoResponse {
+    status: String,
+    message: String,
+    data: SelectorsInfo,
+}
+
+#[get("/admin/selectors")]
+async fn get_selectors_info() -> Result<impl Responder, AppError> {
+    info!("Mendapatkan informasi konfigurasi selector");
+    
+    let response = SelectorsInfoResponse {
+        status: "success".to_string(),
+        message: "Informasi konfigurasi selector berhasil didapatkan".to_string(),
+        data: selectors::info(),
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[post("/admin/selectors/reload")]
+async fn reload_selectors(scraper: web::Data<FilmapikScraper>) -> Result<impl Responder, AppError> {
+    info!("Memuat ulang konfigurasi selector");
+    
+    // Konfigurasi lama tetap dipakai jika konfigurasi baru tidak valid
+    let data = selectors::reload().map_err(|e| {
+        error!("Gagal memuat ulang konfigurasi selector: {}", e);
+        AppError::InternalError(format!("Konfigurasi selector tidak valid: {}", e))
+    })?;
+    
+    // Hasil parse dengan selector lama tidak lagi valid
+    scraper.clear_cache().await;
+    
+    let response = SelectorsInfoResponse {
+        status: "success".to_string(),
+        message: "Konfigurasi selector berhasil dimuat ulang".to_string(),
+        data,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[derive(Serialize)]
+struct SnapshotListResponse {
+    status: String,
+    message: String,
+    data: Vec<SnapshotMeta>,
+}
+
+#[derive(Serialize)]
+struct SnapshotResponse {
+    status: String,
+    message: String,
+    data: SnapshotMeta,
+}
+
+#[get("/admin/snapshots")]
+async fn list_snapshots() -> Result<impl Responder, AppError> {
+    info!("Mendapatkan daftar snapshot HTML");
+    
+    let snapshots = web::block(snapshot::list)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Gagal membaca snapshot: {}", e)))?;
+    
+    let response = SnapshotListResponse {
+        status: "success".to_string(),
+        message: format!("Berhasil mendapatkan {} snapshot", snapshots.len()),
+        data: snapshots,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/admin/snapshots/{snapshot_id}")]
+async fn get_snapshot(snapshot_id: SnapshotId) -> Result<impl Responder, AppError> {
+    let meta = snapshot::get(snapshot_id.as_str())
+        .ok_or_else(|| AppError::NotFoundError(format!("Snapshot {}", snapshot_id)))?;
+    
+    let response = SnapshotResponse {
+        status: "success".to_string(),
+        message: format!("Snapshot {} berhasil didapatkan", snapshot_id),
+        data: meta,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// Mengunduh HTML mentah snapshot, bisa langsung disimpan ke tests/fixtures/
+#[get("/admin/snapshots/{snapshot_id}/html")]
+async fn download_snapshot_html(snapshot_id: SnapshotId) -> Result<impl Responder, AppError> {
+    let path = snapshot::html_path(snapshot_id.as_str());
+    let html = web::block(move || std::fs::read(path))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Gagal membaca snapshot: {}", e)))?
+        .map_err(|_| AppError::NotFoundError(format!("Snapshot {}", snapshot_id)))?;
+    
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .insert_header((
+            http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.html\"", snapshot_id),
+        ))
+        .body(html))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_selectors_info)
+       .service(reload_selectors)
+       .service(list_snapshots)
+       .service(get_snapshot)
+       .service(download_snapshot_html);
+}
@@ -3,7 +3,6 @@ use crate::scraper::FilmapikScraper;
 use actix_web::{get, post, web, HttpResponse, Responder};
 use log::info;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 
 // Struktur respons untuk statistik cache
 #[derive(Serialize)]
@@ -12,6 +11,7 @@ struct CacheStats {
     genres: u64,
     countries: u64,
     details: u64,
+    not_found: u64,
 }
 
 // Struktur respons umum untuk success
@@ -30,11 +30,9 @@ struct CacheStatsResponse {
 }
 
 #[get("/cache/stats")]
-async fn get_cache_stats() -> Result<impl Responder, AppError> {
+async fn get_cache_stats(scraper: web::Data<FilmapikScraper>) -> Result<impl Responder, AppError> {
     info!("Mendapatkan statistik cache");
     
-    let scraper = FilmapikScraper::new();
-    
     // Parse statistik cache dari string ke struct
     let stats_str = scraper.get_cache_stats();
     let parts: Vec<&str> = stats_str.split(", ").collect();
@@ -43,6 +41,7 @@ async fn get_cache_stats() -> Result<impl Responder, AppError> {
     let mut genres: u64 = 0;
     let mut countries: u64 = 0;
     let mut details: u64 = 0;
+    let mut not_found: u64 = 0;
     
     for part in parts {
         if part.contains("Movies:") {
@@ -61,6 +60,10 @@ async fn get_cache_stats() -> Result<impl Responder, AppError> {
             if let Some(val) = part.split(':').nth(1) {
                 details = val.trim().parse().unwrap_or(0);
             }
+        } else if part.contains("NotFound:") {
+            if let Some(val) = part.split(':').nth(1) {
+                not_found = val.trim().parse().unwrap_or(0);
+            }
         }
     }
     
@@ -69,6 +72,7 @@ async fn get_cache_stats() -> Result<impl Responder, AppError> {
         genres,
         countries,
         details,
+        not_found,
     };
     
     let response = CacheStatsResponse {
@@ -81,10 +85,9 @@ async fn get_cache_stats() -> Result<impl Responder, AppError> {
 }
 
 #[post("/cache/clear")]
-async fn clear_cache() -> Result<impl Responder, AppError> {
+async fn clear_cache(scraper: web::Data<FilmapikScraper>) -> Result<impl Responder, AppError> {
     info!("Menghapus semua cache");
     
-    let scraper = FilmapikScraper::new();
     scraper.clear_cache().await;
     
     let response = SuccessResponse {
@@ -101,17 +104,16 @@ struct CachePathParams {
 }
 
 #[post("/cache/refresh/{cache_type}")]
-async fn refresh_cache(path: web::Path<CachePathParams>) -> Result<impl Responder, AppError> {
+async fn refresh_cache(path: web::Path<CachePathParams>, scraper: web::Data<FilmapikScraper>) -> Result<impl Responder, AppError> {
     let cache_type = path.into_inner().cache_type;
     
     info!("Memperbarui cache untuk tipe: {}", cache_type);
     
     // Validasi tipe cache
     if cache_type != "genres" && cache_type != "countries" {
-        return Err(AppError::ScrapingError(format!("Tipe cache tidak valid: {}", cache_type)));
+        return Err(AppError::invalid_parameter("cache_type", format!("Tipe cache tidak valid: {}", cache_type)));
     }
     
-    let scraper = FilmapikScraper::new();
     scraper.refresh_cache(&cache_type).await?;
     
     let response = SuccessResponse {
@@ -1,29 +1,16 @@
 use crate::errors::AppError;
-use crate::models::movie::{CountryResponse, MovieResponse, PaginationInfo};
-use crate::scraper::FilmapikScraper;
+use crate::models::movie::{CountryResponse};
+use crate::api::listing::listing_page;
+use crate::api::params::{ListingQuery, CountryId, Provider};
+use crate::scraper::listing::Listing;
 use actix_web::{get, web, HttpResponse, Responder};
 use log::info;
-use serde::Deserialize;
-
-#[derive(Deserialize)]
-pub struct PaginationParams {
-    #[serde(default = "default_page")]
-    page: u32,
-}
-
-fn default_page() -> u32 {
-    1
-}
-
-// Jumlah film per halaman, biasanya 20 atau 24 film di FilmApik
-const PER_PAGE: u32 = 24;
 
 #[get("/movie/country")]
-pub async fn get_countries() -> Result<impl Responder, AppError> {
+pub async fn get_countries(provider: Provider) -> Result<impl Responder, AppError> {
     info!("Mendapatkan daftar negara film");
     
-    let scraper = FilmapikScraper::new();
-    let countries = scraper.get_countries().await?;
+    let countries = provider.countries().await?;
     
     let response = CountryResponse {
         status: "success".to_string(),
@@ -36,36 +23,19 @@ pub async fn get_countries() -> Result<impl Responder, AppError> {
 
 #[get("/movie/country/{country_id}")]
 pub async fn get_movies_by_country(
-    path: web::Path<String>,
-    query: web::Query<PaginationParams>,
+    country_id: CountryId,
+    params: ListingQuery,
+    provider: Provider,
 ) -> Result<impl Responder, AppError> {
-    let country_id = path.into_inner();
-    let page = query.page;
+    let page = params.paging.page();
     
     info!("Mendapatkan daftar film negara {} halaman {}", country_id, page);
     
-    // Validasi country_id
-    if country_id.is_empty() {
-        return Err(AppError::ScrapingError("Country ID tidak valid".to_string()));
-    }
+    let listing = Listing::Country(country_id.as_str().to_string());
+    let result = listing_page(&provider, &listing, params).await?;
+    let page = result.pagination.current_page;
     
-    let scraper = FilmapikScraper::new();
-    let movies = scraper.get_movies_by_country(&country_id, page).await?;
-    
-    // Buat informasi pagination
-    let pagination = PaginationInfo {
-        current_page: page,
-        per_page: PER_PAGE,
-        total_items: None,
-        total_pages: None,
-    };
-    
-    let response = MovieResponse {
-        status: "success".to_string(),
-        message: format!("Film negara {} halaman {} berhasil didapatkan", country_id, page),
-        pagination,
-        data: movies,
-    };
+    let response = result.into_response(format!("Film negara {} halaman {} berhasil didapatkan", country_id, page));
     
     Ok(HttpResponse::Ok().json(response))
 }
@@ -0,0 +1,78 @@
+use crate::config::CURSOR_SECRET;
+use crate::errors::AppError;
+use crate::scraper::listing::{Anchor, Direction};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Isi cursor listing. Token yang dikirim ke klien berupa "<payload>.<tanda tangan>" dalam base64url,
+// klien tidak perlu (dan tidak bisa) mengubah isinya.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    // Listing dan provider asal cursor
+    pub listing: String,
+    pub provider: String,
+    pub direction: Direction,
+    pub anchor: Anchor,
+    pub limit: u32,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let payload = BASE64.encode(serde_json::to_vec(self).unwrap_or_default());
+        let signature = BASE64.encode(mac(payload.as_bytes()).finalize().into_bytes());
+        format!("{}.{}", payload, signature)
+    }
+
+    pub fn decode(token: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::invalid_parameter("cursor", "Cursor tidak valid atau sudah kedaluwarsa");
+
+        let (payload, signature) = token.trim().split_once('.').ok_or_else(invalid)?;
+        let signature = BASE64.decode(signature).map_err(|_| invalid())?;
+
+        mac(payload.as_bytes()).verify_slice(&signature).map_err(|_| invalid())?;
+
+        let payload = BASE64.decode(payload).map_err(|_| invalid())?;
+        serde_json::from_slice(&payload).map_err(|_| invalid())
+    }
+}
+
+fn mac(payload: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(&CURSOR_SECRET).expect("HMAC menerima kunci dengan panjang berapa pun");
+    mac.update(payload);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> Cursor {
+        Cursor {
+            listing: "genre:action".to_string(),
+            provider: "filmapik".to_string(),
+            direction: Direction::After,
+            anchor: Anchor { id: "creed-iii-2021".to_string(), page: 3 },
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn round_trips_and_rejects_tampering() {
+        let token = cursor().encode();
+        assert_eq!(Cursor::decode(&token).unwrap(), cursor());
+
+        // Payload diganti dengan cursor lain tanpa tanda tangan yang sesuai
+        let mut forged = cursor();
+        forged.anchor.page = 1;
+        let (forged_payload, _) = forged.encode().split_once('.').map(|(p, s)| (p.to_string(), s.to_string())).unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+        for bad in [format!("{}.{}", forged_payload, signature), "bukan-cursor".to_string(), format!("{}.", token)] {
+            assert!(Cursor::decode(&bad).is_err(), "token {:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config::{BATCH_CONCURRENCY, EXPAND_BUDGET_MS};
+use crate::errors::AppError;
+use crate::models::movie::Movie;
+use crate::scraper::SourceProvider;
+use actix_web::dev::Payload;
+use actix_web::{web, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use futures::stream::{self, StreamExt};
+use log::{debug, info};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+
+#[derive(Deserialize)]
+struct ExpandQuery {
+    expand: Option<String>,
+}
+
+// Data tambahan yang diminta lewat `?expand=`, saat ini hanya `detail`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Expand {
+    pub detail: bool,
+}
+
+impl Expand {
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        let mut expand = Expand::default();
+        for value in raw.unwrap_or_default().split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            match value.to_ascii_lowercase().as_str() {
+                "detail" => expand.detail = true,
+                other => {
+                    return Err(AppError::invalid_parameter(
+                        "expand",
+                        format!("Nilai expand tidak dikenal: {} (pilihan: detail)", other),
+                    ))
+                },
+            }
+        }
+        Ok(expand)
+    }
+}
+
+impl FromRequest for Expand {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<ExpandQuery>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("expand", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Expand::parse(query.expand.as_deref()));
+
+        ready(result)
+    }
+}
+
+// Melengkapi film dengan data dari halaman detailnya (cache atau request baru) dalam batas EXPAND_BUDGET_MS.
+// Film yang detailnya gagal atau belum selesai saat batas waktu habis ditandai `expanded: false`
+// dan dikirim apa adanya agar respons tidak tertahan oleh satu halaman detail yang lambat.
+pub(crate) async fn expand_movies(provider: &dyn SourceProvider, movies: &mut [Movie]) {
+    if movies.is_empty() {
+        return;
+    }
+
+    let deadline = Instant::now() + Duration::from_millis(*EXPAND_BUDGET_MS);
+    let ids: Vec<String> = movies.iter().map(|m| m.id.clone()).collect();
+    // Tidak berurutan agar satu detail yang lambat tidak menahan detail berikutnya mulai diambil
+    let details: Vec<_> = stream::iter(ids.into_iter().enumerate())
+        .map(|(index, id)| async move { (index, timeout_at(deadline, provider.detail(&id)).await) })
+        .buffer_unordered(*BATCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut skipped = 0;
+    for (index, result) in details {
+        let movie = &mut movies[index];
+        match result {
+            Ok(Ok(detail)) => movie.expand_with(&detail),
+            Ok(Err(e)) => {
+                debug!("Detail film {} gagal diambil untuk expand: {}", movie.id, e);
+                movie.expanded = Some(false);
+                skipped += 1;
+            },
+            Err(_) => {
+                movie.expanded = Some(false);
+                skipped += 1;
+            },
+        }
+    }
+
+    if skipped > 0 {
+        info!("{} dari {} film tidak di-expand (gagal atau melewati batas {} ms)", skipped, movies.len(), *EXPAND_BUDGET_MS);
+    }
+}
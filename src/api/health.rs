@@ -0,0 +1,43 @@
+use crate::errors::AppError;
+use crate::scraper::canary::{self, ScraperHealth};
+use crate::scraper::FilmapikScraper;
+use actix_web::{get, post, web, HttpResponse, Responder};
+use log::info;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ScraperHealthResponse {
+    status: String,
+    message: String,
+    data: ScraperHealth,
+}
+
+#[get("/health/scraper")]
+async fn get_scraper_health() -> Result<impl Responder, AppError> {
+    let response = ScraperHealthResponse {
+        status: "success".to_string(),
+        message: "Status kesehatan scraper berhasil didapatkan".to_string(),
+        data: canary::current(),
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// Menjalankan pemeriksaan sekarang, misalnya setelah konfigurasi selector dimuat ulang
+#[post("/health/scraper/check")]
+async fn run_scraper_check(scraper: web::Data<FilmapikScraper>) -> Result<impl Responder, AppError> {
+    info!("Menjalankan pemeriksaan kesehatan scraper secara manual");
+    
+    let response = ScraperHealthResponse {
+        status: "success".to_string(),
+        message: "Pemeriksaan kesehatan scraper selesai".to_string(),
+        data: canary::run_check(&scraper).await,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_scraper_health)
+       .service(run_scraper_check);
+}
@@ -0,0 +1,125 @@
+use crate::api::cursor::Cursor;
+use crate::api::expand::expand_movies;
+use crate::api::params::{ListingQuery, Provider};
+use crate::config::SORT_WINDOW_PAGES;
+use crate::errors::AppError;
+use crate::models::movie::{Movie, MovieResponse, PaginationInfo};
+use crate::scraper::listing::{Direction, Listing, ListingRange, UPSTREAM_PER_PAGE};
+
+// Satu halaman listing yang siap dikirim sebagai MovieResponse
+pub(crate) struct ListingPage {
+    pub movies: Vec<Movie>,
+    pub pagination: PaginationInfo,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+impl ListingPage {
+    pub fn into_response(self, message: String) -> MovieResponse {
+        MovieResponse {
+            status: "success".to_string(),
+            message,
+            pagination: self.pagination,
+            next_cursor: self.next_cursor,
+            prev_cursor: self.prev_cursor,
+            data: self.movies,
+        }
+    }
+}
+
+// Mengambil satu halaman listing sesuai parameter klien.
+// - Tanpa `?sort=` dan tanpa per_page/limit/offset: satu halaman upstream diteruskan apa adanya
+// - Dengan per_page/limit/offset: halaman upstream yang mencakup rentang diambil bersamaan lalu digabung
+// - Dengan `?sort=`: beberapa halaman upstream pertama digabung, diurutkan, lalu dipotong sesuai paging
+// - Dengan `?cursor=`: film setelah/sebelum film penanda, tanpa duplikat walaupun listing bergeser
+// Dengan `?expand=detail` setiap film dilengkapi dari halaman detailnya.
+pub(crate) async fn listing_page(provider: &Provider, listing: &Listing, query: ListingQuery) -> Result<ListingPage, AppError> {
+    let expand = query.expand;
+    let mut page = load_page(provider, listing, query).await?;
+    if expand.detail {
+        expand_movies(&**provider, &mut page.movies).await;
+    }
+    Ok(page)
+}
+
+async fn load_page(provider: &Provider, listing: &Listing, query: ListingQuery) -> Result<ListingPage, AppError> {
+    let paging = query.paging;
+    let mut pagination = PaginationInfo {
+        current_page: paging.page(),
+        per_page: paging.limit,
+        total_items: None, // Sulit untuk mendapatkan total item dari scraping
+        total_pages: None, // Sulit untuk mendapatkan total halaman dari scraping
+        offset: (!paging.upstream).then_some(paging.offset),
+    };
+
+    if let Some(sort) = query.sort.0 {
+        let mut movies = provider.window(listing, *SORT_WINDOW_PAGES).await?;
+        sort.sort(&mut movies);
+
+        // Total dihitung dari jendela yang diurutkan, bukan seluruh listing upstream.
+        // Urutan jendela tidak stabil terhadap film baru, jadi cursor tidak dibuat.
+        let total = movies.len() as u32;
+        pagination.total_items = Some(total);
+        pagination.total_pages = Some(total.div_ceil(paging.limit));
+
+        let movies = movies
+            .into_iter()
+            .skip(paging.offset as usize)
+            .take(paging.limit as usize)
+            .collect();
+        return Ok(ListingPage { movies, pagination, next_cursor: None, prev_cursor: None });
+    }
+
+    let (range, limit) = if let Some(cursor) = query.cursor {
+        if cursor.listing != listing.key() || cursor.provider != provider.id() {
+            return Err(AppError::invalid_parameter("cursor", "Cursor berasal dari listing atau provider lain"));
+        }
+        // per_page/limit yang dikirim bersama cursor menggantikan ukuran halaman di cursor
+        let limit = if paging.upstream { cursor.limit } else { paging.limit };
+        let range = provider.around(listing, &cursor.anchor, cursor.direction, limit).await?;
+
+        // Posisi pasti tidak diketahui karena listing bisa bergeser, halaman diperkirakan dari halaman upstream
+        let position = range.first.as_ref().map_or(0, |anchor| (anchor.page - 1) * UPSTREAM_PER_PAGE);
+        pagination.current_page = position / limit + 1;
+        pagination.per_page = limit;
+        pagination.offset = None;
+        (range, limit)
+    } else if !paging.upstream {
+        let range = provider.range(listing, paging.offset, paging.limit).await?;
+        pagination.total_items = range.total_items;
+        pagination.total_pages = range.total_items.map(|total| total.div_ceil(paging.limit));
+        (range, paging.limit)
+    } else {
+        let page = paging.page();
+        let movies = match listing {
+            Listing::Latest => provider.latest(page).await?,
+            Listing::Popular => provider.popular(page).await?,
+            Listing::Genre(genre) => provider.by_genre(genre, page).await?,
+            Listing::Country(country) => provider.by_country(country, page).await?,
+            Listing::Year(year) => provider.by_year(*year, page).await?,
+            Listing::Quality(quality) => provider.by_quality(quality, page).await?,
+            Listing::Search(query) => provider.search(query, page).await?,
+        };
+        (ListingRange::from_page(movies, page), paging.limit)
+    };
+
+    let cursor = |direction: Direction, anchor| {
+        Cursor {
+            listing: listing.key(),
+            provider: provider.id().to_string(),
+            direction,
+            anchor,
+            limit,
+        }
+        .encode()
+    };
+    let next_cursor = range.last.filter(|_| range.has_more).map(|anchor| cursor(Direction::After, anchor));
+    let prev_cursor = range.first.filter(|_| range.has_previous).map(|anchor| cursor(Direction::Before, anchor));
+
+    Ok(ListingPage {
+        movies: range.movies,
+        pagination,
+        next_cursor,
+        prev_cursor,
+    })
+}
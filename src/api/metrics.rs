@@ -0,0 +1,26 @@
+use crate::errors::AppError;
+use crate::metrics::{MetricsSnapshot, METRICS};
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct MetricsResponse {
+    status: String,
+    message: String,
+    data: MetricsSnapshot,
+}
+
+#[get("/metrics")]
+async fn get_metrics() -> Result<impl Responder, AppError> {
+    let response = MetricsResponse {
+        status: "success".to_string(),
+        message: "Metrik berhasil didapatkan".to_string(),
+        data: METRICS.snapshot(),
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_metrics);
+}
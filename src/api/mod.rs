@@ -1,14 +1,35 @@
 use actix_web::web;
 
+pub mod params;
+pub mod listing;
+pub mod cursor;
+pub mod expand;
 pub mod movie;
 pub mod country;
+pub mod year;
+pub mod quality;
+pub mod person;
 pub mod cache;
+pub mod upstream;
+pub mod metrics;
+pub mod admin;
+pub mod health;
+pub mod provider;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api")
+            // Didaftarkan sebelum movie agar /movie/year dan /movie/quality tidak dianggap ID film
+            .configure(year::configure)
+            .configure(quality::configure)
             .configure(movie::configure)
             .configure(country::configure)
+            .configure(person::configure)
             .configure(cache::configure)
+            .configure(upstream::configure)
+            .configure(metrics::configure)
+            .configure(admin::configure)
+            .configure(health::configure)
+            .configure(provider::configure)
     );
 } 
\ No newline at end of file
@@ -1,56 +1,37 @@
+use crate::config::{BATCH_CONCURRENCY, BATCH_MAX_IDS};
 use crate::errors::AppError;
-use crate::models::movie::{MovieResponse, PaginationInfo, GenreResponse, MovieDetailResponse, CountryResponse};
-use crate::scraper::FilmapikScraper;
-use actix_web::{get, web, HttpResponse, Responder, http};
+use crate::models::movie::{PaginationInfo, GenreResponse, MovieDetailResponse, CountryResponse, CatalogResponse, DiscoverResponse, BatchError, BatchItem, BatchResponse};
+use crate::api::expand::{expand_movies, Expand};
+use crate::api::listing::listing_page;
+use crate::api::params::{ListingQuery, DebugFlag, Discover, GenreId, MovieId, Page, Provider, Random, SearchQuery};
+use crate::scraper::listing::Listing;
+use crate::scraper::filmapik::FILMAPIK_PROVIDER_ID;
+use crate::scraper::vcr;
+use actix_web::{get, post, web, HttpResponse, Responder, http};
+use futures::stream::{self, StreamExt};
 use log::info;
 use serde::Deserialize;
-
-#[derive(Deserialize)]
-pub struct PaginationParams {
-    #[serde(default = "default_page")]
-    page: u32,
-}
-
-fn default_page() -> u32 {
-    1
-}
-
-// Jumlah film per halaman, biasanya 20 atau 24 film di FilmApik
-const PER_PAGE: u32 = 24;
+use std::collections::HashSet;
 
 #[get("/movie/latest")]
-async fn get_latest_movies(query: web::Query<PaginationParams>) -> Result<impl Responder, AppError> {
-    let page = query.page;
+async fn get_latest_movies(params: ListingQuery, provider: Provider) -> Result<impl Responder, AppError> {
+    let page = params.paging.page();
     
     info!("Mendapatkan daftar film terbaru halaman {}", page);
     
-    let scraper = FilmapikScraper::new();
-    let movies = scraper.get_latest_movies(page).await?;
+    let result = listing_page(&provider, &Listing::Latest, params).await?;
+    let page = result.pagination.current_page;
     
-    // Buat informasi pagination
-    let pagination = PaginationInfo {
-        current_page: page,
-        per_page: PER_PAGE,
-        total_items: None, // Sulit untuk mendapatkan total item dari scraping
-        total_pages: None, // Sulit untuk mendapatkan total halaman dari scraping
-    };
-    
-    let response = MovieResponse {
-        status: "success".to_string(),
-        message: format!("Film terbaru halaman {} berhasil didapatkan", page),
-        pagination,
-        data: movies,
-    };
+    let response = result.into_response(format!("Film terbaru halaman {} berhasil didapatkan", page));
     
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/movie/genre")]
-async fn get_genres() -> Result<impl Responder, AppError> {
+async fn get_genres(provider: Provider) -> Result<impl Responder, AppError> {
     info!("Mendapatkan daftar genre film");
     
-    let scraper = FilmapikScraper::new();
-    let genres = scraper.get_genres().await?;
+    let genres = provider.genres().await?;
     
     let response = GenreResponse {
         status: "success".to_string(),
@@ -63,73 +44,130 @@ async fn get_genres() -> Result<impl Responder, AppError> {
 
 #[get("/movie/genre/{genre_id}")]
 async fn get_movies_by_genre(
-    path: web::Path<String>,
-    query: web::Query<PaginationParams>,
+    genre_id: GenreId,
+    params: ListingQuery,
+    provider: Provider,
 ) -> Result<impl Responder, AppError> {
-    let genre_id = path.into_inner();
-    let page = query.page;
+    let page = params.paging.page();
     
     info!("Mendapatkan daftar film genre {} halaman {}", genre_id, page);
     
-    // Validasi genre_id
-    if genre_id.is_empty() {
-        return Err(AppError::ScrapingError("Genre ID tidak valid".to_string()));
-    }
+    let listing = Listing::Genre(genre_id.as_str().to_string());
+    let result = listing_page(&provider, &listing, params).await?;
+    let page = result.pagination.current_page;
+    
+    let response = result.into_response(format!("Film genre {} halaman {} berhasil didapatkan", genre_id, page));
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/popular")]
+async fn get_popular_movies(params: ListingQuery, provider: Provider) -> Result<impl Responder, AppError> {
+    let page = params.paging.page();
     
-    let scraper = FilmapikScraper::new();
-    let movies = scraper.get_movies_by_genre(&genre_id, page).await?;
+    info!("Mendapatkan daftar film populer (rating tertinggi) halaman {}", page);
+    
+    let result = listing_page(&provider, &Listing::Popular, params).await?;
+    let page = result.pagination.current_page;
+    
+    let response = result.into_response(format!("Film populer (rating tertinggi) halaman {} berhasil didapatkan", page));
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/search")]
+async fn search_movies(query: SearchQuery, params: ListingQuery, provider: Provider) -> Result<impl Responder, AppError> {
+    let page = params.paging.page();
+    
+    info!("Mencari film \"{}\" halaman {}", query, page);
+    
+    let listing = Listing::Search(query.as_str().to_string());
+    let result = listing_page(&provider, &listing, params).await?;
+    let page = result.pagination.current_page;
+    
+    let response = result.into_response(format!("Hasil pencarian \"{}\" halaman {} berhasil didapatkan", query, page));
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/discover")]
+async fn discover_movies(discover: Discover, page: Page, provider: Provider) -> Result<impl Responder, AppError> {
+    let page = *page;
+    
+    info!("Discover film {:?} urutan {:?} halaman {}", discover.filters, discover.sort, page);
+    
+    let result = provider.discover(&discover.filters, discover.sort, page).await?;
     
-    // Buat informasi pagination
     let pagination = PaginationInfo {
-        current_page: page,
-        per_page: PER_PAGE,
-        total_items: None,
-        total_pages: None,
+        current_page: result.page,
+        per_page: result.per_page,
+        total_items: result.total_items,
+        total_pages: result.total_pages,
+        offset: None,
     };
     
-    let response = MovieResponse {
+    let response = DiscoverResponse {
         status: "success".to_string(),
-        message: format!("Film genre {} halaman {} berhasil didapatkan", genre_id, page),
+        message: format!("Hasil discover halaman {} berhasil didapatkan", page),
         pagination,
-        data: movies,
+        strategy: result.strategy,
+        partial: result.partial,
+        note: result.note,
+        data: result.movies,
     };
     
     Ok(HttpResponse::Ok().json(response))
 }
 
-#[get("/movie/popular")]
-async fn get_popular_movies(query: web::Query<PaginationParams>) -> Result<impl Responder, AppError> {
-    let page = query.page;
+#[get("/movie/random")]
+async fn get_random_movie(random: Random, provider: Provider) -> Result<impl Responder, AppError> {
+    info!("Memilih film acak {:?} dengan seed {:?}", random.filters, random.seed);
     
-    info!("Mendapatkan daftar film populer (rating tertinggi) halaman {}", page);
+    let mut movie_detail = provider.random(&random.filters, random.seed).await?;
+    movie_detail.parse_warnings = None;
+    
+    let response = MovieDetailResponse {
+        status: "success".to_string(),
+        message: format!("Film acak {} berhasil didapatkan", movie_detail.title),
+        data: movie_detail,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/catalog")]
+async fn get_catalog(page: Page, provider: Provider) -> Result<impl Responder, AppError> {
+    let page = *page;
     
-    let scraper = FilmapikScraper::new();
-    let movies = scraper.get_popular_movies(page).await?;
+    info!("Mendapatkan katalog film halaman {}", page);
     
-    // Buat informasi pagination
+    let catalog = provider.catalog(page).await?;
+    
+    // Total hanya diketahui jika katalog diambil dari REST API atau sitemap WordPress
     let pagination = PaginationInfo {
-        current_page: page,
-        per_page: PER_PAGE,
-        total_items: None, // Sulit untuk mendapatkan total item dari scraping
-        total_pages: None, // Sulit untuk mendapatkan total halaman dari scraping
+        current_page: catalog.page,
+        per_page: catalog.per_page,
+        total_items: catalog.total_items,
+        total_pages: catalog.total_pages,
+        offset: None,
     };
     
-    let response = MovieResponse {
+    let response = CatalogResponse {
         status: "success".to_string(),
-        message: format!("Film populer (rating tertinggi) halaman {} berhasil didapatkan", page),
+        message: format!("Katalog film halaman {} berhasil didapatkan", page),
+        source: catalog.source,
         pagination,
-        data: movies,
+        data: catalog.entries,
     };
     
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/movie/country")]
-async fn get_countries() -> Result<impl Responder, AppError> {
+async fn get_countries(provider: Provider) -> Result<impl Responder, AppError> {
     info!("Mendapatkan daftar negara film");
     
-    let scraper = FilmapikScraper::new();
-    let countries = scraper.get_countries().await?;
+    let countries = provider.countries().await?;
     
     let response = CountryResponse {
         status: "success".to_string(),
@@ -140,19 +178,88 @@ async fn get_countries() -> Result<impl Responder, AppError> {
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[derive(Deserialize)]
+struct BatchRequest {
+    ids: Vec<String>,
+}
+
+// Detail beberapa film sekaligus. ID yang sama hanya diambil sekali, ID yang tidak valid atau gagal
+// diambil dilaporkan per ID tanpa menggagalkan seluruh batch.
+#[post("/movie/batch")]
+async fn get_movie_batch(body: web::Bytes, debug: DebugFlag, provider: Provider) -> Result<impl Responder, AppError> {
+    let request: BatchRequest = serde_json::from_slice(&body)
+        .map_err(|e| AppError::invalid_parameter("ids", format!("Body harus berupa JSON {{\"ids\": [...]}}: {}", e)))?;
+
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = request
+        .ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+    if ids.is_empty() || ids.len() > *BATCH_MAX_IDS {
+        return Err(AppError::invalid_parameter(
+            "ids",
+            format!("Jumlah ID film harus antara 1 dan {}", *BATCH_MAX_IDS),
+        ));
+    }
+
+    info!("Mendapatkan detail {} film sekaligus", ids.len());
+
+    let debug = debug.0;
+    let provider = &provider;
+    let items: Vec<BatchItem> = stream::iter(ids)
+        .map(|id| async move {
+            let result = match MovieId::parse(&id) {
+                Ok(movie_id) => provider.detail(movie_id.as_str()).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(mut detail) => {
+                    if !debug {
+                        detail.parse_warnings = None;
+                    }
+                    BatchItem { id, status: "success".to_string(), data: Some(detail), error: None }
+                },
+                Err(e) => BatchItem {
+                    id,
+                    status: "error".to_string(),
+                    data: None,
+                    error: Some(BatchError { code: e.code(), message: e.to_string() }),
+                },
+            }
+        })
+        .buffered(*BATCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let failed = items.iter().filter(|item| item.error.is_some()).count();
+    let response = BatchResponse {
+        status: if failed == 0 { "success" } else { "partial" }.to_string(),
+        message: format!("{} dari {} detail film berhasil didapatkan", items.len() - failed, items.len()),
+        succeeded: items.len() - failed,
+        failed,
+        data: items,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 #[get("/movie/{movie_id}")]
-async fn get_movie_detail(path: web::Path<String>) -> Result<impl Responder, AppError> {
-    let movie_id = path.into_inner();
-    
+async fn get_movie_detail(movie_id: MovieId, debug: DebugFlag, expand: Expand, provider: Provider) -> Result<impl Responder, AppError> {
     info!("Mendapatkan detail film dengan ID: {}", movie_id);
     
-    // Validasi movie_id
-    if movie_id.is_empty() {
-        return Err(AppError::ScrapingError("Movie ID tidak valid".to_string()));
-    }
+    let mut movie_detail = provider.detail(movie_id.as_str()).await?;
     
-    let scraper = FilmapikScraper::new();
-    let movie_detail = scraper.get_movie_detail(&movie_id).await?;
+    // Peringatan parse hanya dikirim jika diminta dengan ?debug=true
+    if !debug.0 {
+        movie_detail.parse_warnings = None;
+    }
+
+    // Film terkait dari halaman detail hanya berisi judul dan poster
+    if expand.detail {
+        expand_movies(&*provider, &mut movie_detail.related_movies).await;
+    }
     
     let response = MovieDetailResponse {
         status: "success".to_string(),
@@ -164,25 +271,20 @@ async fn get_movie_detail(path: web::Path<String>) -> Result<impl Responder, App
 }
 
 #[get("/movie/{movie_id}/watch")]
-async fn get_movie_watch_url(path: web::Path<String>) -> Result<impl Responder, AppError> {
-    let movie_id = path.into_inner();
-    
+async fn get_movie_watch_url(movie_id: MovieId, provider: Provider) -> Result<impl Responder, AppError> {
     info!("Menampilkan halaman iframe untuk film dengan ID: {}", movie_id);
     
-    // Validasi movie_id
-    if movie_id.is_empty() {
-        return Err(AppError::ScrapingError("Movie ID tidak valid".to_string()));
-    }
-    
-    let scraper = FilmapikScraper::new();
-    let movie_detail = scraper.get_movie_detail(&movie_id).await?;
+    let movie_detail = provider.detail(movie_id.as_str()).await?;
     
     // Cek apakah watch_url tersedia
     if let Some(watch_url) = movie_detail.watch_url {
         info!("Membuat iframe untuk URL video: {}", watch_url);
         
-        // Buat URL proxy untuk bypass CSP
-        let proxy_url = format!("/api/movie/{}/watch/proxy", movie_id);
+        // Buat URL proxy untuk bypass CSP, provider selain FilmApik ikut diteruskan
+        let mut proxy_url = format!("/api/movie/{}/watch/proxy", movie_id);
+        if provider.id() != FILMAPIK_PROVIDER_ID {
+            proxy_url.push_str(&format!("?provider={}", provider.id()));
+        }
         
         // Buat HTML dengan iframe untuk menampilkan video melalui proxy
         let html = format!(r#"<!DOCTYPE html>
@@ -254,23 +356,15 @@ async fn get_movie_watch_url(path: web::Path<String>) -> Result<impl Responder,
             .content_type("text/html; charset=utf-8")
             .body(html))
     } else {
-        Err(AppError::ScrapingError("URL video tidak ditemukan".to_string()))
+        Err(AppError::NotFoundError("URL video tidak ditemukan".to_string()))
     }
 }
 
 #[get("/movie/{movie_id}/watch/proxy")]
-async fn proxy_video_content(path: web::Path<String>) -> Result<impl Responder, AppError> {
-    let movie_id = path.into_inner();
-    
+async fn proxy_video_content(movie_id: MovieId, provider: Provider) -> Result<impl Responder, AppError> {
     info!("Memproxy konten video untuk film dengan ID: {}", movie_id);
     
-    // Validasi movie_id
-    if movie_id.is_empty() {
-        return Err(AppError::ScrapingError("Movie ID tidak valid".to_string()));
-    }
-    
-    let scraper = FilmapikScraper::new();
-    let movie_detail = scraper.get_movie_detail(&movie_id).await?;
+    let movie_detail = provider.detail(movie_id.as_str()).await?;
     
     // Cek apakah watch_url tersedia
     if let Some(watch_url) = movie_detail.watch_url {
@@ -280,11 +374,11 @@ async fn proxy_video_content(path: web::Path<String>) -> Result<impl Responder,
         let client = reqwest::Client::new();
         
         // Mengirim permintaan ke URL asli dengan header yang lebih lengkap
-        let response = client.get(&watch_url)
+        let request = client.get(&watch_url)
             .header("Accept-Language", "en-US,en;q=0.9")
             .header("Connection", "keep-alive")
             .header("Host", "filmdewasa.org")
-            .header("Referer", "http://194.102.105.201/")
+            .header("Referer", format!("{}/", provider.base_url()))
             .header("sec-ch-ua", "\"Chromium\";v=\"134\", \"Not:A-Brand\";v=\"24\", \"Microsoft Edge\";v=\"134\"")
             .header("sec-ch-ua-mobile", "?0")
             .header("sec-ch-ua-platform", "\"Windows\"")
@@ -293,16 +387,21 @@ async fn proxy_video_content(path: web::Path<String>) -> Result<impl Responder,
             .header("Sec-Fetch-Site", "cross-site")
             .header("Sec-Fetch-Storage-Access", "active")
             .header("Upgrade-Insecure-Requests", "1")
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/134.0.0.0 Safari/537.36 Edg/134.0.0.0")
-            .send()
+            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/134.0.0.0 Safari/537.36 Edg/134.0.0.0");
+        
+        // Request proxy juga melewati mode record/replay
+        let response = vcr::send(&client, request)
             .await
-            .map_err(|e| AppError::ScrapingError(format!("Gagal memproxy konten: {}", e)))?;
+            .map_err(|e| match e {
+                AppError::HttpError(e) => AppError::ScrapingError(format!("Gagal memproxy konten: {}", e)),
+                other => other,
+            })?;
         
         // Membuat response builder
         let mut builder = HttpResponse::Ok();
         
         // Salin header yang relevan
-        for (key, value) in response.headers().iter() {
+        for (key, value) in response.headers.iter() {
             // Filter header yang tidak ingin diikutkan
             let key_str = key.as_str();
             if !key_str.eq_ignore_ascii_case("content-length") &&
@@ -324,20 +423,16 @@ async fn proxy_video_content(path: web::Path<String>) -> Result<impl Responder,
         builder.append_header((http::header::CONTENT_SECURITY_POLICY, "frame-ancestors *"));
         
         // Setel content-type jika tersedia
-        if let Some(content_type) = response.headers().get("content-type") {
+        if let Some(content_type) = response.headers.get("content-type") {
             if let Ok(content_type_str) = content_type.to_str() {
                 builder.content_type(content_type_str);
             }
         }
         
-        // Ambil body respons
-        let body_bytes = response.bytes().await
-            .map_err(|e| AppError::ScrapingError(format!("Gagal membaca body: {}", e)))?;
-        
         // Kembalikan respons proxy
-        Ok(builder.body(body_bytes))
+        Ok(builder.body(response.body))
     } else {
-        Err(AppError::ScrapingError("URL video tidak ditemukan".to_string()))
+        Err(AppError::NotFoundError("URL video tidak ditemukan".to_string()))
     }
 }
 
@@ -346,7 +441,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
        .service(get_genres)
        .service(get_movies_by_genre)
        .service(get_popular_movies)
+       .service(search_movies)
+       .service(get_catalog)
+       .service(discover_movies)
+       .service(get_random_movie)
        .service(get_countries)
+       .service(get_movie_batch)
        .service(get_movie_detail)
        .service(get_movie_watch_url)
        .service(proxy_video_content);
@@ -0,0 +1,830 @@
+use crate::api::cursor::Cursor;
+use crate::api::expand::Expand;
+use crate::config::MAX_PAGE;
+use crate::errors::AppError;
+use crate::scraper::discover::{DiscoverFilters, DiscoverSort};
+use crate::scraper::listing::{ListingSort, UPSTREAM_PER_PAGE};
+use crate::scraper::random::RandomFilters;
+use crate::scraper::{ProviderRegistry, SourceProvider};
+use actix_web::dev::Payload;
+use actix_web::{web, FromRequest, HttpRequest};
+use chrono::{Datelike, Utc};
+use futures::future::{ready, Ready};
+use serde::Deserialize;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+// Panjang maksimum slug yang diteruskan ke upstream
+const MAX_SLUG_LEN: usize = 200;
+
+// Panjang maksimum kata kunci pencarian
+const MAX_QUERY_LEN: usize = 100;
+
+// Tahun rilis terlama yang boleh diminta
+const MIN_RELEASE_YEAR: i32 = 1900;
+
+// Jumlah film maksimum per halaman yang bisa diminta klien
+pub const MAX_PER_PAGE: u32 = 100;
+
+// Slug WordPress hanya berisi huruf, angka, '-' dan '_'.
+// Karakter lain ('/', '.', '?', '%', spasi, dll) ditolak agar tidak bisa mengubah URL upstream.
+fn validate_slug(parameter: &str, label: &str, raw: &str) -> Result<String, AppError> {
+    if raw.is_empty() {
+        return Err(AppError::invalid_parameter(parameter, format!("{} tidak boleh kosong", label)));
+    }
+
+    if raw.len() > MAX_SLUG_LEN {
+        return Err(AppError::invalid_parameter(
+            parameter,
+            format!("{} terlalu panjang (maksimum {} karakter)", label, MAX_SLUG_LEN),
+        ));
+    }
+
+    if !raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(AppError::invalid_parameter(
+            parameter,
+            format!("{} tidak valid: hanya boleh berisi huruf, angka, '-' dan '_'", label),
+        ));
+    }
+
+    Ok(raw.to_string())
+}
+
+// Membuat newtype slug yang diekstrak dan divalidasi dari path parameter
+macro_rules! slug_param {
+    ($name:ident, $param:literal, $label:literal) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn parse(raw: &str) -> Result<Self, AppError> {
+                validate_slug($param, $label, raw).map(Self)
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromRequest for $name {
+            type Error = AppError;
+            type Future = Ready<Result<Self, AppError>>;
+
+            fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+                ready(Self::parse(req.match_info().get($param).unwrap_or_default()))
+            }
+        }
+    };
+}
+
+slug_param!(MovieId, "movie_id", "Movie ID");
+slug_param!(GenreId, "genre_id", "Genre ID");
+slug_param!(CountryId, "country_id", "Country ID");
+slug_param!(SnapshotId, "snapshot_id", "Snapshot ID");
+slug_param!(QualityId, "quality", "Quality");
+slug_param!(PersonId, "person_id", "Person ID");
+
+// Tahun rilis dari path parameter `{year}`, empat digit antara MIN_RELEASE_YEAR dan tahun depan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Year(pub i32);
+
+impl Year {
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let max = Utc::now().year() + 1;
+        let invalid = || {
+            AppError::invalid_parameter(
+                "year",
+                format!("Tahun harus berupa angka antara {} dan {}", MIN_RELEASE_YEAR, max),
+            )
+        };
+
+        if raw.len() != 4 || !raw.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let year = raw.parse::<i32>().map_err(|_| invalid())?;
+        if !(MIN_RELEASE_YEAR..=max).contains(&year) {
+            return Err(invalid());
+        }
+
+        Ok(Year(year))
+    }
+}
+
+impl Deref for Year {
+    type Target = i32;
+
+    fn deref(&self) -> &i32 {
+        &self.0
+    }
+}
+
+impl FromRequest for Year {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Self::parse(req.match_info().get("year").unwrap_or_default()))
+    }
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<String>,
+}
+
+// Nomor halaman dari query `?page=`, default 1 dan dibatasi 1..=MAX_PAGE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page(pub u32);
+
+impl Page {
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        let Some(raw) = raw else {
+            return Ok(Page(1));
+        };
+
+        let invalid = || {
+            AppError::invalid_parameter(
+                "page",
+                format!("Halaman harus berupa angka antara 1 dan {}", *MAX_PAGE),
+            )
+        };
+
+        let page = raw.trim().parse::<u32>().map_err(|_| invalid())?;
+        if page == 0 || page > *MAX_PAGE {
+            return Err(invalid());
+        }
+
+        Ok(Page(page))
+    }
+}
+
+impl Deref for Page {
+    type Target = u32;
+
+    fn deref(&self) -> &u32 {
+        &self.0
+    }
+}
+
+impl FromRequest for Page {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<PageQuery>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("page", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Page::parse(query.page.as_deref()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct PagingParams {
+    page: Option<String>,
+    per_page: Option<String>,
+    limit: Option<String>,
+    offset: Option<String>,
+}
+
+// Paging listing dari `?page=` ditambah `?per_page=` (alias `?limit=`) atau `?offset=`.
+// Tanpa per_page/limit/offset halaman upstream diteruskan apa adanya.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paging {
+    pub offset: u32,
+    pub limit: u32,
+    // true jika klien hanya memakai ?page= sehingga satu halaman upstream cukup
+    pub upstream: bool,
+}
+
+// Offset terbesar yang bisa diminta, yaitu film terakhir di halaman MAX_PAGE upstream.
+// Dihitung dengan saturating agar MAX_PAGE yang sangat besar atau 0 tidak overflow.
+fn max_offset(max_page: u32) -> u32 {
+    max_page.saturating_mul(UPSTREAM_PER_PAGE).saturating_sub(1)
+}
+
+// Offset film pertama di halaman klien, ditolak jika melebihi max_offset atau tidak muat di u32
+fn page_offset(page: u32, limit: u32, max_offset: u32) -> Result<u32, AppError> {
+    page.saturating_sub(1)
+        .checked_mul(limit)
+        .filter(|offset| *offset <= max_offset)
+        .ok_or_else(|| AppError::invalid_parameter("page", format!("Halaman {} melebihi batas {} film", page, max_offset.saturating_add(1))))
+}
+
+impl Paging {
+    fn parse(params: PagingParams) -> Result<Self, AppError> {
+        let present = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let number = |parameter: &str, raw: String, min: u32, max: u32| {
+            raw.parse::<u32>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(|| AppError::invalid_parameter(parameter, format!("{} harus berupa angka antara {} dan {}", parameter, min, max)))
+        };
+
+        let page = present(params.page);
+        let per_page = present(params.per_page).map(|raw| number("per_page", raw, 1, MAX_PER_PAGE)).transpose()?;
+        let limit = present(params.limit).map(|raw| number("limit", raw, 1, MAX_PER_PAGE)).transpose()?;
+        let max_offset = max_offset(*MAX_PAGE);
+        let offset = present(params.offset).map(|raw| number("offset", raw, 0, max_offset)).transpose()?;
+
+        if per_page.is_some() && limit.is_some() && per_page != limit {
+            return Err(AppError::invalid_parameter("limit", "limit dan per_page tidak boleh berbeda"));
+        }
+        if offset.is_some() && page.is_some() {
+            return Err(AppError::invalid_parameter("offset", "offset tidak bisa dipakai bersama page"));
+        }
+
+        let page = *Page::parse(page.as_deref())?;
+        let (limit, upstream) = match per_page.or(limit) {
+            Some(limit) => (limit, false),
+            None => (UPSTREAM_PER_PAGE, offset.is_none()),
+        };
+        let offset = match offset {
+            Some(offset) => offset,
+            None => page_offset(page, limit, max_offset)?,
+        };
+        Ok(Paging { offset, limit, upstream })
+    }
+
+    // Nomor halaman klien, dihitung dari offset jika offset bukan kelipatan limit
+    pub fn page(&self) -> u32 {
+        self.offset / self.limit + 1
+    }
+}
+
+impl FromRequest for Paging {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<PagingParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("page", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Paging::parse(query.into_inner()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchQueryParams {
+    q: Option<String>,
+}
+
+// Kata kunci pencarian dari query `?q=`, wajib diisi dan dibatasi MAX_QUERY_LEN karakter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery(String);
+
+impl SearchQuery {
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        let query = raw.map(str::trim).unwrap_or_default();
+
+        if query.is_empty() {
+            return Err(AppError::invalid_parameter("q", "Kata kunci pencarian tidak boleh kosong"));
+        }
+
+        if query.chars().count() > MAX_QUERY_LEN {
+            return Err(AppError::invalid_parameter(
+                "q",
+                format!("Kata kunci pencarian terlalu panjang (maksimum {} karakter)", MAX_QUERY_LEN),
+            ));
+        }
+
+        Ok(SearchQuery(query.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromRequest for SearchQuery {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<SearchQueryParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("q", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| SearchQuery::parse(query.q.as_deref()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct DebugParams {
+    debug: Option<String>,
+}
+
+// Flag `?debug=` untuk menyertakan parse_warnings: true/1 atau false/0, kosong berarti false
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DebugFlag(pub bool);
+
+impl DebugFlag {
+    pub fn parse(raw: Option<&str>) -> Result<Self, AppError> {
+        match raw.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("false") | Some("0") => Ok(DebugFlag(false)),
+            Some("true") | Some("1") => Ok(DebugFlag(true)),
+            Some(_) => Err(AppError::invalid_parameter("debug", "Parameter debug harus true atau false")),
+        }
+    }
+}
+
+impl FromRequest for DebugFlag {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<DebugParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("debug", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| DebugFlag::parse(query.debug.as_deref()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct DiscoverParams {
+    genre: Option<String>,
+    country: Option<String>,
+    year: Option<String>,
+    quality: Option<String>,
+    min_rating: Option<String>,
+    sort: Option<String>,
+}
+
+// Filter dan urutan /movie/discover. Parameter kosong (misalnya `?genre=`) dianggap tidak diisi.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discover {
+    pub filters: DiscoverFilters,
+    pub sort: DiscoverSort,
+}
+
+impl Discover {
+    fn parse(params: DiscoverParams) -> Result<Self, AppError> {
+        let present = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let slug = |parameter: &str, label: &str, value: Option<String>| {
+            present(value)
+                .map(|v| validate_slug(parameter, label, &v.to_ascii_lowercase()))
+                .transpose()
+        };
+
+        let min_rating = present(params.min_rating).map(|raw| parse_min_rating(&raw)).transpose()?;
+
+        let sort = DiscoverSort::parse(params.sort.as_deref().unwrap_or_default())
+            .map_err(|e| AppError::invalid_parameter("sort", e))?;
+
+        Ok(Discover {
+            filters: DiscoverFilters {
+                genre: slug("genre", "Genre", params.genre)?,
+                country: slug("country", "Country", params.country)?,
+                year: present(params.year).map(|raw| Year::parse(&raw).map(|y| y.0)).transpose()?,
+                quality: slug("quality", "Quality", params.quality)?,
+                min_rating,
+            },
+            sort,
+        })
+    }
+}
+
+impl FromRequest for Discover {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<DiscoverParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("query", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Discover::parse(query.into_inner()));
+
+        ready(result)
+    }
+}
+
+fn parse_min_rating(raw: &str) -> Result<f32, AppError> {
+    raw.parse::<f32>()
+        .ok()
+        .filter(|rating| (0.0..=10.0).contains(rating))
+        .ok_or_else(|| AppError::invalid_parameter("min_rating", "Rating minimum harus berupa angka antara 0 dan 10"))
+}
+
+#[derive(Deserialize)]
+struct RandomParams {
+    genre: Option<String>,
+    country: Option<String>,
+    year_from: Option<String>,
+    year_to: Option<String>,
+    min_rating: Option<String>,
+    seed: Option<String>,
+}
+
+// Filter dan seed /movie/random. Parameter kosong dianggap tidak diisi.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Random {
+    pub filters: RandomFilters,
+    pub seed: Option<u64>,
+}
+
+impl Random {
+    fn parse(params: RandomParams) -> Result<Self, AppError> {
+        let present = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let slug = |parameter: &str, label: &str, value: Option<String>| {
+            present(value)
+                .map(|v| validate_slug(parameter, label, &v.to_ascii_lowercase()))
+                .transpose()
+        };
+        // Validasi tahun sama dengan `?year=`, hanya nama parameternya yang berbeda
+        let year = |parameter: &str, value: Option<String>| {
+            present(value)
+                .map(|raw| {
+                    Year::parse(&raw).map(|y| y.0).map_err(|_| {
+                        AppError::invalid_parameter(
+                            parameter,
+                            format!("Tahun harus berupa angka antara {} dan {}", MIN_RELEASE_YEAR, Utc::now().year() + 1),
+                        )
+                    })
+                })
+                .transpose()
+        };
+
+        let year_from = year("year_from", params.year_from)?;
+        let year_to = year("year_to", params.year_to)?;
+        if let (Some(from), Some(to)) = (year_from, year_to) {
+            if from > to {
+                return Err(AppError::invalid_parameter("year_to", "year_to tidak boleh lebih kecil dari year_from"));
+            }
+        }
+
+        let seed = present(params.seed)
+            .map(|raw| {
+                raw.parse::<u64>()
+                    .map_err(|_| AppError::invalid_parameter("seed", "Seed harus berupa bilangan bulat positif"))
+            })
+            .transpose()?;
+
+        Ok(Random {
+            filters: RandomFilters {
+                genre: slug("genre", "Genre", params.genre)?,
+                country: slug("country", "Country", params.country)?,
+                year_from,
+                year_to,
+                min_rating: present(params.min_rating).map(|raw| parse_min_rating(&raw)).transpose()?,
+            },
+            seed,
+        })
+    }
+}
+
+impl FromRequest for Random {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<RandomParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("query", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Random::parse(query.into_inner()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct SortParams {
+    sort: Option<String>,
+    order: Option<String>,
+}
+
+// Urutan listing dari `?sort=` dan `?order=`, None berarti urutan upstream
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sort(pub Option<ListingSort>);
+
+impl Sort {
+    fn parse(params: SortParams) -> Result<Self, AppError> {
+        let present = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let order = present(params.order)
+            .map(|raw| ListingSort::parse_order(&raw).map_err(|e| AppError::invalid_parameter("order", e)))
+            .transpose()?;
+
+        match present(params.sort) {
+            Some(raw) => {
+                let field = ListingSort::parse_field(&raw).map_err(|e| AppError::invalid_parameter("sort", e))?;
+                Ok(Sort(Some(ListingSort::new(field, order))))
+            },
+            None if order.is_some() => Err(AppError::invalid_parameter("order", "Parameter order hanya bisa dipakai bersama sort")),
+            None => Ok(Sort(None)),
+        }
+    }
+}
+
+impl FromRequest for Sort {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = web::Query::<SortParams>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("query", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| Sort::parse(query.into_inner()));
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct CursorParams {
+    cursor: Option<String>,
+    page: Option<String>,
+    offset: Option<String>,
+}
+
+// Parameter endpoint listing film: paging, urutan, `?expand=` dan cursor `?cursor=` dari next_cursor/prev_cursor.
+// Cursor menentukan posisi sendiri sehingga tidak bisa digabung dengan page, offset atau sort.
+#[derive(Debug, Clone)]
+pub struct ListingQuery {
+    pub paging: Paging,
+    pub sort: Sort,
+    pub cursor: Option<Cursor>,
+    pub expand: Expand,
+}
+
+impl FromRequest for ListingQuery {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let result = (|| {
+            let paging = Paging::from_request(req, payload).into_inner()?;
+            let sort = Sort::from_request(req, payload).into_inner()?;
+            let expand = Expand::from_request(req, payload).into_inner()?;
+            let params = web::Query::<CursorParams>::from_query(req.query_string())
+                .map_err(|e| AppError::invalid_parameter("cursor", format!("Query string tidak valid: {}", e)))?
+                .into_inner();
+
+            let Some(token) = params.cursor.filter(|c| !c.trim().is_empty()) else {
+                return Ok(ListingQuery { paging, sort, cursor: None, expand });
+            };
+            if params.page.is_some() || params.offset.is_some() || sort.0.is_some() {
+                return Err(AppError::invalid_parameter("cursor", "cursor tidak bisa dipakai bersama page, offset atau sort"));
+            }
+            Ok(ListingQuery { paging, sort, cursor: Some(Cursor::decode(&token)?), expand })
+        })();
+
+        ready(result)
+    }
+}
+
+#[derive(Deserialize)]
+struct ProviderQuery {
+    provider: Option<String>,
+}
+
+// Provider sumber data dari query `?provider=`, default FilmApik
+#[derive(Clone)]
+pub struct Provider(Arc<dyn SourceProvider>);
+
+impl Provider {
+    pub fn into_inner(self) -> Arc<dyn SourceProvider> {
+        self.0
+    }
+}
+
+impl Deref for Provider {
+    type Target = dyn SourceProvider;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl FromRequest for Provider {
+    type Error = AppError;
+    type Future = Ready<Result<Self, AppError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let Some(registry) = req.app_data::<web::Data<ProviderRegistry>>() else {
+            return ready(Err(AppError::InternalError("Registry provider tidak dikonfigurasi".to_string())));
+        };
+
+        let result = web::Query::<ProviderQuery>::from_query(req.query_string())
+            .map_err(|e| AppError::invalid_parameter("provider", format!("Query string tidak valid: {}", e)))
+            .and_then(|query| registry.get(query.provider.as_deref()))
+            .map(Provider);
+
+        ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use actix_web::ResponseError;
+    use actix_web::http::StatusCode;
+
+    #[test]
+    fn slug_accepts_wordpress_slugs() {
+        assert_eq!(MovieId::parse("paayum-oli-nee-yenakku-2023").unwrap().as_str(), "paayum-oli-nee-yenakku-2023");
+        assert!(GenreId::parse("science_fiction").is_ok());
+        assert!(CountryId::parse("USA").is_ok());
+    }
+
+    #[test]
+    fn slug_rejects_path_and_query_characters() {
+        for raw in ["", "..", "a/b", "../etc", "a?b=1", "a#b", "a%2Fb", "a b", "film.html", "a&b", "é"] {
+            let err = MovieId::parse(raw).unwrap_err();
+            assert_eq!(err.status_code(), StatusCode::BAD_REQUEST, "input {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn slug_rejects_overlong_values() {
+        assert!(GenreId::parse(&"a".repeat(MAX_SLUG_LEN)).is_ok());
+        assert!(GenreId::parse(&"a".repeat(MAX_SLUG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn page_defaults_to_one() {
+        assert_eq!(Page::parse(None).unwrap(), Page(1));
+    }
+
+    #[test]
+    fn page_rejects_out_of_range_values() {
+        assert_eq!(Page::parse(Some("1")).unwrap(), Page(1));
+        assert_eq!(Page::parse(Some(&MAX_PAGE.to_string())).unwrap(), Page(*MAX_PAGE));
+
+        let too_big = (*MAX_PAGE + 1).to_string();
+        for raw in ["0", "-1", "abc", "", "1.5", too_big.as_str(), "99999999999"] {
+            assert!(Page::parse(Some(raw)).is_err(), "input {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn year_accepts_four_digit_years_in_range() {
+        assert_eq!(Year::parse("2023").unwrap(), Year(2023));
+        assert_eq!(Year::parse("1900").unwrap(), Year(1900));
+
+        let next_next = (Utc::now().year() + 2).to_string();
+        for raw in ["", "23", "02023", "1899", "20a3", "+202", next_next.as_str()] {
+            assert!(Year::parse(raw).is_err(), "input {:?}", raw);
+        }
+    }
+
+    #[actix_web::test]
+    async fn page_extractor_reads_query_string() {
+        let (req, mut payload) = TestRequest::get().uri("/movie/latest?page=3").to_http_parts();
+        assert_eq!(Page::from_request(&req, &mut payload).await.unwrap(), Page(3));
+
+        let (req, mut payload) = TestRequest::get().uri("/movie/latest?page=0").to_http_parts();
+        let err = Page::from_request(&req, &mut payload).await.unwrap_err();
+        assert_eq!(err.code(), crate::errors::ErrorCode::InvalidParameter);
+    }
+
+    #[test]
+    fn search_query_is_trimmed_and_limited() {
+        assert_eq!(SearchQuery::parse(Some("  rumah dara ")).unwrap().as_str(), "rumah dara");
+        assert!(SearchQuery::parse(Some(&"é".repeat(MAX_QUERY_LEN))).is_ok());
+
+        let too_long = "a".repeat(MAX_QUERY_LEN + 1);
+        for raw in [None, Some(""), Some("   "), Some(too_long.as_str())] {
+            assert!(SearchQuery::parse(raw).is_err(), "input {:?}", raw);
+        }
+    }
+
+    #[actix_web::test]
+    async fn discover_extractor_validates_facets() {
+        let (req, mut payload) = TestRequest::get()
+            .uri("/movie/discover?genre=Horror&country=korea&year=2023&quality=&min_rating=6.5&sort=rating")
+            .to_http_parts();
+        let discover = Discover::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(discover.filters.genre.as_deref(), Some("horror"));
+        assert_eq!(discover.filters.year, Some(2023));
+        assert_eq!(discover.filters.quality, None);
+        assert_eq!(discover.filters.min_rating, Some(6.5));
+        assert_eq!(discover.sort, DiscoverSort::Rating);
+
+        for (query, parameter) in [("genre=a/b", "genre"), ("year=23", "year"), ("min_rating=11", "min_rating"), ("sort=random", "sort")] {
+            let (req, mut payload) = TestRequest::get().uri(&format!("/movie/discover?{}", query)).to_http_parts();
+            let err = Discover::from_request(&req, &mut payload).await.unwrap_err();
+            assert_eq!(err.code(), crate::errors::ErrorCode::InvalidParameter, "query {:?}", query);
+            assert!(format!("{:?}", err).contains(parameter), "query {:?}", query);
+        }
+    }
+
+    #[actix_web::test]
+    async fn random_extractor_validates_year_range_and_seed() {
+        let random = |query: &str| {
+            let (req, mut payload) = TestRequest::get().uri(&format!("/movie/random?{}", query)).to_http_parts();
+            Random::from_request(&req, &mut payload).into_inner()
+        };
+
+        let parsed = random("genre=Drama&year_from=2010&year_to=2012&min_rating=7&seed=42").unwrap();
+        assert_eq!(parsed.filters.genre.as_deref(), Some("drama"));
+        assert_eq!((parsed.filters.year_from, parsed.filters.year_to), (Some(2010), Some(2012)));
+        assert_eq!(parsed.filters.min_rating, Some(7.0));
+        assert_eq!(parsed.seed, Some(42));
+        assert_eq!(random("").unwrap().filters, RandomFilters::default());
+
+        for (query, parameter) in [("year_from=20", "year_from"), ("year_from=2012&year_to=2010", "year_to"), ("seed=-1", "seed"), ("min_rating=11", "min_rating")] {
+            let err = random(query).unwrap_err();
+            assert!(format!("{:?}", err).contains(parameter), "query {:?}", query);
+        }
+    }
+
+    #[actix_web::test]
+    async fn paging_maps_client_page_size_to_offset() {
+        let paging = |query: &str| {
+            let (req, mut payload) = TestRequest::get().uri(&format!("/movie/latest?{}", query)).to_http_parts();
+            Paging::from_request(&req, &mut payload).into_inner()
+        };
+
+        assert_eq!(paging("page=3").unwrap(), Paging { offset: 48, limit: 24, upstream: true });
+        assert_eq!(paging("page=3&per_page=10").unwrap(), Paging { offset: 20, limit: 10, upstream: false });
+        assert_eq!(paging("limit=60&offset=90").unwrap(), Paging { offset: 90, limit: 60, upstream: false });
+        assert_eq!(paging("limit=60&offset=90").unwrap().page(), 2);
+        assert_eq!(paging("offset=5").unwrap(), Paging { offset: 5, limit: 24, upstream: false });
+
+        for (query, parameter) in [
+            ("per_page=0", "per_page"),
+            ("limit=101", "limit"),
+            ("limit=10&per_page=20", "limit"),
+            ("page=2&offset=10", "offset"),
+            ("offset=-1", "offset"),
+            (&format!("offset={}", max_offset(*MAX_PAGE) + 1), "offset"),
+            (&format!("page={}&per_page={}", *MAX_PAGE, MAX_PER_PAGE), "page"),
+        ] {
+            let err = paging(query).unwrap_err();
+            assert!(format!("{:?}", err).contains(parameter), "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn paging_bounds_do_not_overflow() {
+        assert_eq!(max_offset(0), 0);
+        assert_eq!(max_offset(u32::MAX), u32::MAX - 1);
+
+        assert_eq!(page_offset(1, 24, max_offset(0)).unwrap(), 0);
+        assert!(page_offset(2, 24, max_offset(0)).is_err());
+        assert_eq!(page_offset(3, 100, max_offset(500)).unwrap(), 200);
+
+        // Perkalian yang melewati u32 ditolak, bukan wrap atau panic
+        let err = page_offset(u32::MAX, MAX_PER_PAGE, max_offset(u32::MAX)).unwrap_err();
+        assert!(format!("{:?}", err).contains("page"));
+    }
+
+    #[actix_web::test]
+    async fn sort_extractor_defaults_order_per_field() {
+        use crate::scraper::listing::{SortField, SortOrder};
+
+        let (req, mut payload) = TestRequest::get().uri("/movie/latest?sort=Title").to_http_parts();
+        let sort = Sort::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(sort.0, Some(ListingSort { field: SortField::Title, order: SortOrder::Asc }));
+
+        let (req, mut payload) = TestRequest::get().uri("/movie/latest?sort=rating&order=asc").to_http_parts();
+        let sort = Sort::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(sort.0, Some(ListingSort { field: SortField::Rating, order: SortOrder::Asc }));
+
+        let (req, mut payload) = TestRequest::get().uri("/movie/latest?sort=").to_http_parts();
+        assert_eq!(Sort::from_request(&req, &mut payload).await.unwrap(), Sort(None));
+
+        for (query, parameter) in [("sort=latest", "sort"), ("sort=year&order=up", "order"), ("order=desc", "order")] {
+            let (req, mut payload) = TestRequest::get().uri(&format!("/movie/latest?{}", query)).to_http_parts();
+            let err = Sort::from_request(&req, &mut payload).await.unwrap_err();
+            assert!(format!("{:?}", err).contains(parameter), "query {:?}", query);
+        }
+    }
+
+    #[actix_web::test]
+    async fn slug_extractor_reads_path_parameter() {
+        let (req, mut payload) = TestRequest::get()
+            .uri("/movie/genre/horror")
+            .param("genre_id", "horror")
+            .to_http_parts();
+        assert_eq!(GenreId::from_request(&req, &mut payload).await.unwrap().as_str(), "horror");
+
+        let (req, mut payload) = TestRequest::get()
+            .uri("/movie/genre/..")
+            .param("genre_id", "..")
+            .to_http_parts();
+        assert!(GenreId::from_request(&req, &mut payload).await.is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::errors::AppError;
+use crate::models::movie::{PaginationInfo, PersonResponse};
+use crate::api::params::{Page, PersonId, Provider};
+use actix_web::{get, web, HttpResponse, Responder};
+use log::info;
+
+// Jumlah film per halaman untuk satu peran, biasanya 20 atau 24 film di FilmApik
+const PER_PAGE: u32 = 24;
+
+#[get("/person/{person_id}")]
+pub async fn get_person(
+    person_id: PersonId,
+    page: Page,
+    provider: Provider,
+) -> Result<impl Responder, AppError> {
+    let page = *page;
+    
+    info!("Mendapatkan daftar film orang {} halaman {}", person_id, page);
+    
+    let person_movies = provider.person(person_id.as_str(), page).await?;
+    
+    // Setiap halaman menggabungkan halaman yang sama dari daftar pemeran dan sutradara
+    let pagination = PaginationInfo {
+        current_page: page,
+        per_page: PER_PAGE,
+        total_items: None,
+        total_pages: None,
+        offset: None,
+    };
+    
+    let response = PersonResponse {
+        status: "success".to_string(),
+        message: format!("Film {} halaman {} berhasil didapatkan", person_movies.person.name, page),
+        pagination,
+        data: person_movies,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_person);
+}
@@ -0,0 +1,29 @@
+use crate::errors::AppError;
+use crate::scraper::provider::{ProviderInfo, ProviderRegistry};
+use actix_web::{get, web, HttpResponse, Responder};
+use log::info;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ProviderListResponse {
+    status: String,
+    message: String,
+    data: Vec<ProviderInfo>,
+}
+
+#[get("/providers")]
+async fn get_providers(registry: web::Data<ProviderRegistry>) -> Result<impl Responder, AppError> {
+    info!("Mendapatkan daftar provider");
+    
+    let response = ProviderListResponse {
+        status: "success".to_string(),
+        message: "Daftar provider berhasil didapatkan".to_string(),
+        data: registry.list(),
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_providers);
+}
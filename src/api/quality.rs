@@ -0,0 +1,47 @@
+use crate::errors::AppError;
+use crate::models::movie::{TermResponse};
+use crate::api::listing::listing_page;
+use crate::api::params::{ListingQuery, Provider, QualityId};
+use crate::scraper::listing::Listing;
+use crate::scraper::wordpress::Taxonomy;
+use actix_web::{get, web, HttpResponse, Responder};
+use log::info;
+
+#[get("/movie/quality")]
+pub async fn get_qualities(provider: Provider) -> Result<impl Responder, AppError> {
+    info!("Mendapatkan daftar kualitas film");
+    
+    let qualities = provider.terms(Taxonomy::Quality).await?;
+    
+    let response = TermResponse {
+        status: "success".to_string(),
+        message: "Daftar kualitas film berhasil didapatkan".to_string(),
+        data: qualities,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/quality/{quality}")]
+pub async fn get_movies_by_quality(
+    quality: QualityId,
+    params: ListingQuery,
+    provider: Provider,
+) -> Result<impl Responder, AppError> {
+    let page = params.paging.page();
+    
+    info!("Mendapatkan daftar film kualitas {} halaman {}", quality, page);
+    
+    let listing = Listing::Quality(quality.as_str().to_string());
+    let result = listing_page(&provider, &listing, params).await?;
+    let page = result.pagination.current_page;
+    
+    let response = result.into_response(format!("Film kualitas {} halaman {} berhasil didapatkan", quality, page));
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_qualities)
+       .service(get_movies_by_quality);
+}
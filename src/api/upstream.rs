@@ -0,0 +1,44 @@
+use crate::config;
+use crate::errors::AppError;
+use crate::scraper::domain::{self, DomainChangeEvent};
+use actix_web::{get, web, HttpResponse, Responder};
+use log::info;
+use serde::Serialize;
+
+// Informasi domain upstream yang sedang digunakan
+#[derive(Serialize)]
+struct UpstreamInfo {
+    base_url: String,
+    configured_url: String,
+    domain_changes: Vec<DomainChangeEvent>,
+}
+
+#[derive(Serialize)]
+struct UpstreamInfoResponse {
+    status: String,
+    message: String,
+    data: UpstreamInfo,
+}
+
+#[get("/upstream")]
+async fn get_upstream_info() -> Result<impl Responder, AppError> {
+    info!("Mendapatkan informasi domain upstream");
+    
+    let data = UpstreamInfo {
+        base_url: config::filmapik_url(),
+        configured_url: config::FILMAPIK_URL.clone(),
+        domain_changes: domain::domain_events(),
+    };
+    
+    let response = UpstreamInfoResponse {
+        status: "success".to_string(),
+        message: "Informasi upstream berhasil didapatkan".to_string(),
+        data,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_upstream_info);
+}
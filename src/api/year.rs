@@ -0,0 +1,48 @@
+use crate::errors::AppError;
+use crate::models::movie::{TermResponse};
+use crate::api::listing::listing_page;
+use crate::api::params::{ListingQuery, Provider, Year};
+use crate::scraper::listing::Listing;
+use crate::scraper::wordpress::Taxonomy;
+use actix_web::{get, web, HttpResponse, Responder};
+use log::info;
+
+#[get("/movie/year")]
+pub async fn get_years(provider: Provider) -> Result<impl Responder, AppError> {
+    info!("Mendapatkan daftar tahun rilis film");
+    
+    let years = provider.terms(Taxonomy::Year).await?;
+    
+    let response = TermResponse {
+        status: "success".to_string(),
+        message: "Daftar tahun rilis film berhasil didapatkan".to_string(),
+        data: years,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/movie/year/{year}")]
+pub async fn get_movies_by_year(
+    year: Year,
+    params: ListingQuery,
+    provider: Provider,
+) -> Result<impl Responder, AppError> {
+    let year = *year;
+    let page = params.paging.page();
+    
+    info!("Mendapatkan daftar film tahun {} halaman {}", year, page);
+    
+    let listing = Listing::Year(year);
+    let result = listing_page(&provider, &listing, params).await?;
+    let page = result.pagination.current_page;
+    
+    let response = result.into_response(format!("Film tahun {} halaman {} berhasil didapatkan", year, page));
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_years)
+       .service(get_movies_by_year);
+}
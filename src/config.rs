@@ -1,18 +1,69 @@
 use lazy_static::lazy_static;
+use log::{error, info};
 use std::env;
-use std::sync::Mutex;
+use std::fs;
+use std::sync::RwLock;
 
 lazy_static! {
     pub static ref APP_HOST: String = env::var("APP_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
     pub static ref APP_PORT: u16 = env::var("APP_PORT").map(|p| p.parse().unwrap_or(8080)).unwrap_or(8080);
     pub static ref FILMAPIK_URL: String = env::var("FILMAPIK_URL").unwrap_or_else(|_| "http://194.102.105.201".to_string());
+    // File opsional untuk menyimpan base URL terbaru jika domain FilmApik berpindah
+    pub static ref FILMAPIK_URL_STATE_FILE: Option<String> = env::var("FILMAPIK_URL_STATE_FILE").ok().filter(|s| !s.is_empty());
+    // Jumlah redirect berturut-turut ke domain yang sama sebelum base URL diganti
+    pub static ref DOMAIN_MOVE_THRESHOLD: u32 = env::var("DOMAIN_MOVE_THRESHOLD").map(|v| v.parse().unwrap_or(3)).unwrap_or(3).max(1);
+    // Nomor halaman tertinggi yang boleh diminta client
+    pub static ref MAX_PAGE: u32 = env::var("MAX_PAGE").map(|v| v.parse().unwrap_or(500)).unwrap_or(500).max(1);
+    // Interval pemeriksaan kesehatan scraper dalam detik, 0 untuk menonaktifkan
+    pub static ref CANARY_INTERVAL_SECS: u64 = env::var("CANARY_INTERVAL_SECS").map(|v| v.parse().unwrap_or(900)).unwrap_or(900);
+    // ID film yang dipakai untuk memeriksa halaman detail, jika tidak diset memakai film pertama di halaman terbaru
+    pub static ref CANARY_MOVIE_ID: Option<String> = env::var("CANARY_MOVIE_ID").ok().filter(|s| !s.is_empty());
+    // Jumlah minimum film yang harus ditemukan di halaman terbaru
+    pub static ref CANARY_MIN_ITEMS: usize = env::var("CANARY_MIN_ITEMS").map(|v| v.parse().unwrap_or(10)).unwrap_or(10);
+    // Direktori penyimpanan snapshot HTML halaman yang gagal di-parse
+    pub static ref SNAPSHOT_DIR: String = env::var("SNAPSHOT_DIR")
+        .unwrap_or_else(|_| env::temp_dir().join("filmapik-snapshots").to_string_lossy().into_owned());
+    // Ukuran total maksimum direktori snapshot dalam byte, 0 untuk menonaktifkan snapshot
+    pub static ref SNAPSHOT_MAX_BYTES: u64 = env::var("SNAPSHOT_MAX_BYTES").map(|v| v.parse().unwrap_or(50 * 1024 * 1024)).unwrap_or(50 * 1024 * 1024);
+    // Umur maksimum snapshot dalam detik
+    pub static ref SNAPSHOT_MAX_AGE_SECS: u64 = env::var("SNAPSHOT_MAX_AGE_SECS").map(|v| v.parse().unwrap_or(7 * 86400)).unwrap_or(7 * 86400);
+    // Mode client upstream: live, record (simpan response ke cassette), atau replay (hanya dari cassette)
+    pub static ref UPSTREAM_MODE: String = env::var("UPSTREAM_MODE").unwrap_or_else(|_| "live".to_string());
+    // Direktori file cassette untuk mode record/replay
+    pub static ref CASSETTE_DIR: String = env::var("CASSETTE_DIR").unwrap_or_else(|_| "cassettes".to_string());
+    // Situs lain dengan tema yang sama, format "id=url" dipisahkan koma
+    pub static ref EXTRA_PROVIDERS: String = env::var("EXTRA_PROVIDERS").unwrap_or_default();
+    // Batas halaman upstream yang diambil untuk satu request discover
+    pub static ref DISCOVER_MAX_PAGES: u32 = env::var("DISCOVER_MAX_PAGES").map(|v| v.parse().unwrap_or(20)).unwrap_or(20).max(1);
+    // Batas halaman upstream yang diambil untuk satu request film acak, dihitung dari semua listing sumber
+    pub static ref RANDOM_MAX_PAGES: u32 = env::var("RANDOM_MAX_PAGES").map(|v| v.parse().unwrap_or(20)).unwrap_or(20).max(1);
+    // Jumlah halaman upstream yang diurutkan bersama saat memakai `?sort=` di listing
+    pub static ref SORT_WINDOW_PAGES: u32 = env::var("SORT_WINDOW_PAGES").map(|v| v.parse().unwrap_or(5)).unwrap_or(5).clamp(1, 20);
+    // Kunci HMAC untuk menandatangani cursor listing. Jika tidak diset dibuat acak saat start,
+    // sehingga cursor lama tidak berlaku lagi setelah restart.
+    pub static ref CURSOR_SECRET: Vec<u8> = env::var("CURSOR_SECRET")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .map(String::into_bytes)
+        .unwrap_or_else(|| rand::random::<[u8; 32]>().to_vec());
+    // Jumlah maksimum ID film dalam satu request POST /movie/batch
+    pub static ref BATCH_MAX_IDS: usize = env::var("BATCH_MAX_IDS").map(|v| v.parse().unwrap_or(50)).unwrap_or(50).max(1);
+    // Jumlah detail film yang diambil bersamaan untuk satu request batch atau ?expand=detail
+    pub static ref BATCH_CONCURRENCY: usize = env::var("BATCH_CONCURRENCY").map(|v| v.parse().unwrap_or(6)).unwrap_or(6).max(1);
+    // Batas waktu (ms) untuk melengkapi film dengan ?expand=detail, film yang belum selesai dikirim tanpa detail
+    pub static ref EXPAND_BUDGET_MS: u64 = env::var("EXPAND_BUDGET_MS").map(|v| v.parse().unwrap_or(2000)).unwrap_or(2000);
+    // Sumber daftar genre/negara/katalog: html (scraping halaman) atau wordpress (REST API dan sitemap, fallback ke HTML)
+    pub static ref CATALOG_SOURCE: String = env::var("CATALOG_SOURCE").unwrap_or_else(|_| "html".to_string());
+
+    // Base URL yang sedang aktif, bisa berubah saat runtime
+    static ref ACTIVE_FILMAPIK_URL: RwLock<String> = RwLock::new(initial_filmapik_url());
 }
 
 // Fungsi untuk inisialisasi konfigurasi
 pub fn init() {
     // Membaca variabel lingkungan dari file .env jika ada
     dotenv::dotenv().ok();
-    
+
     // Memastikan logger sudah diinisialisasi
     if env::var("RUST_LOG").is_err() {
         // Menggunakan blok unsafe karena set_var adalah fungsi unsafe
@@ -21,4 +72,44 @@ pub fn init() {
         }
     }
     env_logger::init();
-} 
\ No newline at end of file
+
+    info!("Base URL FilmApik aktif: {}", filmapik_url());
+}
+
+// Base URL awal: diambil dari file state jika ada, jika tidak dari FILMAPIK_URL
+fn initial_filmapik_url() -> String {
+    if let Some(path) = FILMAPIK_URL_STATE_FILE.as_ref() {
+        if let Ok(content) = fs::read_to_string(path) {
+            let url = content.trim();
+            if !url.is_empty() {
+                return url.trim_end_matches('/').to_string();
+            }
+        }
+    }
+
+    FILMAPIK_URL.trim_end_matches('/').to_string()
+}
+
+// Mengambil base URL FilmApik yang sedang aktif (tanpa trailing slash)
+pub fn filmapik_url() -> String {
+    ACTIVE_FILMAPIK_URL
+        .read()
+        .map(|url| url.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().clone())
+}
+
+// Mengganti base URL FilmApik yang aktif dan menyimpannya ke file state jika dikonfigurasi
+pub fn set_filmapik_url(url: &str) {
+    let url = url.trim_end_matches('/').to_string();
+
+    match ACTIVE_FILMAPIK_URL.write() {
+        Ok(mut active) => *active = url.clone(),
+        Err(poisoned) => *poisoned.into_inner() = url.clone(),
+    }
+
+    if let Some(path) = FILMAPIK_URL_STATE_FILE.as_ref() {
+        if let Err(e) = fs::write(path, format!("{}\n", url)) {
+            error!("Gagal menyimpan base URL ke {}: {}", path, e);
+        }
+    }
+}
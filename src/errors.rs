@@ -1,39 +1,175 @@
-use actix_web::{HttpResponse, http::StatusCode, ResponseError};
+#[cfg(feature = "server")]
+use crate::request_id;
+#[cfg(feature = "server")]
+use actix_web::{HttpResponse, http::{header, StatusCode}, ResponseError};
 use serde::Serialize;
+#[cfg(feature = "server")]
+use serde_json::json;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Error saat scraping: {0}")]
     ScrapingError(String),
-    
+
     #[error("Error saat request HTTP: {0}")]
     HttpError(#[from] reqwest::Error),
-    
+
     #[error("Error saat mengolah JSON: {0}")]
     JsonError(#[from] serde_json::Error),
-    
+
     #[error("Error internal: {0}")]
     InternalError(String),
-    
+
     #[error("Tidak ditemukan: {0}")]
     NotFoundError(String),
+
+    #[error("{message}")]
+    ValidationError { parameter: String, message: String },
+
+    #[error("Upstream mengembalikan status HTTP {status}: {url}")]
+    UpstreamStatus { url: String, status: u16 },
+
+    #[error("Tidak ditemukan: {url}")]
+    UpstreamNotFound { url: String },
+
+    #[error("Upstream memblokir request: {url}: {reason}")]
+    UpstreamBlocked { url: String, reason: String },
+
+    #[error("Sudah tidak tersedia: {url}")]
+    Gone { url: String },
+
+    #[error("Upstream membatasi jumlah request: {url}")]
+    RateLimited { url: String, retry_after: u64 },
+}
+
+// Kode error yang stabil agar client bisa melakukan branching tanpa membaca pesan
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ScrapingFailed,
+    UpstreamTimeout,
+    UpstreamUnavailable,
+    UpstreamError,
+    UpstreamBlocked,
+    UpstreamRateLimited,
+    NotFound,
+    Gone,
+    InvalidParameter,
+    InternalError,
+}
+
+// Informasi request ke upstream yang menyebabkan error
+#[cfg(feature = "server")]
+#[derive(Debug, Serialize)]
+struct UpstreamContext {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
 }
 
+#[cfg(feature = "server")]
 #[derive(Serialize)]
 struct ErrorResponse {
     status: &'static str,
     message: String,
+    code: ErrorCode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream: Option<UpstreamContext>,
+}
+
+impl AppError {
+    // Membuat error validasi untuk parameter request
+    pub fn invalid_parameter(parameter: &str, message: impl Into<String>) -> Self {
+        AppError::ValidationError {
+            parameter: parameter.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::ScrapingError(_) => ErrorCode::ScrapingFailed,
+            AppError::HttpError(e) if e.is_timeout() => ErrorCode::UpstreamTimeout,
+            AppError::HttpError(e) if e.is_connect() => ErrorCode::UpstreamUnavailable,
+            AppError::HttpError(_) => ErrorCode::UpstreamError,
+            AppError::JsonError(_) => ErrorCode::InternalError,
+            AppError::InternalError(_) => ErrorCode::InternalError,
+            AppError::NotFoundError(_) => ErrorCode::NotFound,
+            AppError::ValidationError { .. } => ErrorCode::InvalidParameter,
+            AppError::UpstreamStatus { .. } => ErrorCode::UpstreamError,
+            AppError::UpstreamNotFound { .. } => ErrorCode::NotFound,
+            AppError::UpstreamBlocked { .. } => ErrorCode::UpstreamBlocked,
+            AppError::Gone { .. } => ErrorCode::Gone,
+            AppError::RateLimited { .. } => ErrorCode::UpstreamRateLimited,
+        }
+    }
+}
+
+// Body dan status response HTTP untuk error, hanya dipakai oleh server (feature `server`)
+#[cfg(feature = "server")]
+impl AppError {
+    // Detail tambahan yang bisa dibaca mesin
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            AppError::ValidationError { parameter, .. } => Some(json!({ "parameter": parameter })),
+            AppError::UpstreamBlocked { reason, .. } => Some(json!({ "reason": reason })),
+            AppError::RateLimited { retry_after, .. } => Some(json!({ "retry_after": retry_after })),
+            _ => None,
+        }
+    }
+
+    fn upstream(&self) -> Option<UpstreamContext> {
+        match self {
+            AppError::HttpError(e) => e.url().map(|url| UpstreamContext {
+                url: url.to_string(),
+                status: e.status().map(|s| s.as_u16()),
+            }),
+            AppError::UpstreamStatus { url, status } => Some(UpstreamContext {
+                url: url.clone(),
+                status: Some(*status),
+            }),
+            AppError::UpstreamNotFound { url } => Some(UpstreamContext {
+                url: url.clone(),
+                status: Some(404),
+            }),
+            AppError::Gone { url } => Some(UpstreamContext {
+                url: url.clone(),
+                status: Some(410),
+            }),
+            AppError::RateLimited { url, .. } => Some(UpstreamContext {
+                url: url.clone(),
+                status: Some(429),
+            }),
+            AppError::UpstreamBlocked { url, .. } => Some(UpstreamContext {
+                url: url.clone(),
+                status: None,
+            }),
+            _ => None,
+        }
+    }
 }
 
+#[cfg(feature = "server")]
 impl ResponseError for AppError {
     fn status_code(&self) -> StatusCode {
         match self {
             AppError::ScrapingError(_) => StatusCode::BAD_GATEWAY,
+            AppError::HttpError(e) if e.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
             AppError::HttpError(_) => StatusCode::BAD_GATEWAY,
             AppError::JsonError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotFoundError(_) => StatusCode::NOT_FOUND,
+            AppError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+            AppError::UpstreamStatus { .. } => StatusCode::BAD_GATEWAY,
+            AppError::UpstreamNotFound { .. } => StatusCode::NOT_FOUND,
+            AppError::UpstreamBlocked { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Gone { .. } => StatusCode::GONE,
+            AppError::RateLimited { .. } => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -42,8 +178,19 @@ impl ResponseError for AppError {
         let error_response = ErrorResponse {
             status: "error",
             message: self.to_string(),
+            code: self.code(),
+            details: self.details(),
+            request_id: request_id::current(),
+            upstream: self.upstream(),
         };
-        
-        HttpResponse::build(status).json(error_response)
+
+        let mut builder = HttpResponse::build(status);
+
+        // Teruskan Retry-After dari upstream agar client tahu kapan boleh mencoba lagi
+        if let AppError::RateLimited { retry_after, .. } = self {
+            builder.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+
+        builder.json(error_response)
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,25 @@
+// Library FilmApik: scraper, model data, dan tipe error bisa dipakai oleh service lain tanpa
+// menjalankan server HTTP. Layer HTTP (Actix) ada di balik feature `server` (aktif secara default):
+//
+//     filmapik-api = { path = "...", default-features = false }
+//
+// API publik yang stabil (mengikuti semver, perubahan yang tidak kompatibel hanya di versi mayor):
+// - `scraper::FilmapikScraper` beserta method publiknya
+// - `models` (Movie, MovieDetail, Country, response API, dan tipe lainnya)
+// - `errors::AppError` dan `errors::ErrorCode`
+//
+// Modul lain (config, metrics, parser, selectors, vcr, dan sebagainya) ikut diekspor untuk test,
+// benchmark, dan fuzzing, tetapi dianggap detail internal yang bisa berubah kapan saja.
+
+#[cfg(feature = "server")]
+pub mod api;
+pub mod config;
+pub mod errors;
+pub mod metrics;
+pub mod models;
+#[cfg(feature = "server")]
+pub mod request_id;
+pub mod scraper;
+
+pub use errors::{AppError, ErrorCode};
+pub use scraper::FilmapikScraper;
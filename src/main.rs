@@ -1,11 +1,7 @@
-mod api;
-mod config;
-mod errors;
-mod models;
-mod scraper;
-
 use actix_cors::Cors;
 use actix_web::{middleware, web, App, HttpServer};
+use filmapik_api::scraper::{canary, selectors, vcr, wordpress, FilmapikScraper, ProviderRegistry};
+use filmapik_api::{api, config, request_id};
 use log::info;
 
 #[actix_web::main]
@@ -13,12 +9,32 @@ async fn main() -> std::io::Result<()> {
     // Inisialisasi konfigurasi
     config::init();
     
+    // Muat dan kompilasi konfigurasi selector CSS di awal, aplikasi berhenti jika ada yang tidak valid
+    selectors::init();
+    
+    // Validasi mode client upstream (live/record/replay)
+    vcr::init();
+    
+    // Validasi sumber daftar genre/negara/katalog (html/wordpress)
+    wordpress::init();
+    
     let host = &*config::APP_HOST;
     let port = *config::APP_PORT;
     
     info!("Memulai server FilmApik API pada {}:{}", host, port);
     
-    HttpServer::new(|| {
+    // Satu instance scraper dipakai bersama oleh semua worker agar cache-nya tidak hilang per request
+    let scraper = web::Data::new(FilmapikScraper::new());
+    
+    // FilmApik sebagai provider default ditambah situs lain dari EXTRA_PROVIDERS
+    let providers = web::Data::new(
+        ProviderRegistry::from_config(scraper.get_ref()).unwrap_or_else(|e| panic!("{}", e))
+    );
+    
+    // Pemeriksaan berkala untuk mendeteksi perubahan HTML FilmApik
+    canary::spawn(scraper.get_ref().clone());
+    
+    HttpServer::new(move || {
         // Konfigurasi CORS
         let cors = Cors::default()
             .allow_any_origin()
@@ -29,6 +45,9 @@ async fn main() -> std::io::Result<()> {
         App::new()
             .wrap(middleware::Logger::default())
             .wrap(cors)
+            .wrap(middleware::from_fn(request_id::middleware))
+            .app_data(scraper.clone())
+            .app_data(providers.clone())
             .service(web::scope("").configure(api::configure))
     })
     .bind(format!("{}:{}", host, port))?
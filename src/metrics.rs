@@ -0,0 +1,68 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+// Counter sederhana untuk memantau kondisi scraper
+pub struct Metrics {
+    upstream_requests: AtomicU64,
+    upstream_blocked: AtomicU64,
+    details_parsed: AtomicU64,
+    // Jumlah peringatan parse per field halaman detail
+    parse_warnings: Mutex<BTreeMap<String, u64>>,
+}
+
+pub static METRICS: Metrics = Metrics::new();
+
+// Salinan nilai counter pada satu waktu
+#[derive(Debug, Serialize)]
+pub struct MetricsSnapshot {
+    pub upstream_requests: u64,
+    pub upstream_blocked: u64,
+    pub details_parsed: u64,
+    pub parse_warnings: BTreeMap<String, u64>,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            upstream_requests: AtomicU64::new(0),
+            upstream_blocked: AtomicU64::new(0),
+            details_parsed: AtomicU64::new(0),
+            parse_warnings: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    // Dipanggil setiap kali request dikirim ke upstream
+    pub fn record_upstream_request(&self) {
+        self.upstream_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Dipanggil saat upstream menyajikan halaman challenge/captcha atau halaman kosong yang mencurigakan
+    pub fn record_upstream_blocked(&self) {
+        self.upstream_blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Dipanggil setiap kali halaman detail di-parse, mengembalikan jumlah total halaman detail yang sudah di-parse
+    pub fn record_detail_parse<'a>(&self, warning_fields: impl IntoIterator<Item = &'a str>) -> u64 {
+        let mut parse_warnings = self.parse_warnings.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for field in warning_fields {
+            *parse_warnings.entry(field.to_string()).or_insert(0) += 1;
+        }
+        
+        self.details_parsed.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            upstream_requests: self.upstream_requests.load(Ordering::Relaxed),
+            upstream_blocked: self.upstream_blocked.load(Ordering::Relaxed),
+            details_parsed: self.details_parsed.load(Ordering::Relaxed),
+            parse_warnings: self
+                .parse_warnings
+                .lock()
+                .map(|warnings| warnings.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
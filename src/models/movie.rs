@@ -1,5 +1,5 @@
+use crate::errors::ErrorCode;
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Movie {
@@ -11,6 +11,42 @@ pub struct Movie {
     pub quality: Option<String>,
     pub genres: Vec<String>,
     pub url: String,
+    // ID provider sumber data (misalnya "filmapik"), ID film hanya unik di dalam satu provider
+    #[serde(default)]
+    pub provider: String,
+    // Jumlah penonton, hanya diisi dengan ?expand=detail atau saat diurutkan berdasarkan views dan detail film sudah ada di cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub views: Option<u32>,
+    // Field dari halaman detail, hanya diisi dengan ?expand=detail
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub director: Option<String>,
+    // Dengan ?expand=detail: true jika detail berhasil digabung, false jika gagal atau melewati batas waktu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<bool>,
+}
+
+impl Movie {
+    // Melengkapi film dari halaman detailnya. Nilai dari listing tetap dipakai jika sudah ada.
+    pub fn expand_with(&mut self, detail: &MovieDetail) {
+        self.year = self.year.or(detail.year);
+        self.rating = self.rating.or(detail.rating);
+        self.quality = self.quality.take().or_else(|| detail.quality.clone());
+        if self.genres.is_empty() {
+            self.genres = detail.genres.clone();
+        }
+        self.views = self.views.or(detail.views);
+        self.description = detail.description.clone();
+        self.duration = detail.duration.clone();
+        self.country = detail.country.clone();
+        self.director = detail.director.clone();
+        self.expanded = Some(true);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +55,9 @@ pub struct PaginationInfo {
     pub per_page: u32,
     pub total_items: Option<u32>,
     pub total_pages: Option<u32>,
+    // Posisi film pertama, hanya ada jika klien memakai per_page/limit/offset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +65,11 @@ pub struct MovieResponse {
     pub status: String,
     pub message: String,
     pub pagination: PaginationInfo,
+    // Cursor untuk halaman berikutnya/sebelumnya, dikirim kembali lewat `?cursor=`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
     pub data: Vec<Movie>,
 }
 
@@ -34,6 +78,9 @@ pub struct Genre {
     pub id: String,
     pub name: String,
     pub url: String,
+    // Jumlah film, hanya tersedia jika daftar diambil dari REST API WordPress
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -43,6 +90,34 @@ pub struct GenreResponse {
     pub data: Vec<Genre>,
 }
 
+// Hasil satu ID film di POST /movie/batch, kegagalan satu ID tidak menggagalkan ID lain
+#[derive(Debug, Serialize)]
+pub struct BatchItem {
+    pub id: String,
+    // "success" atau "error"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<MovieDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResponse {
+    // "success" jika semua ID berhasil, "partial" jika ada yang gagal
+    pub status: String,
+    pub message: String,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub data: Vec<BatchItem>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MovieDetail {
     pub id: String,
@@ -61,9 +136,37 @@ pub struct MovieDetail {
     pub language: Option<String>,
     pub director: Option<String>,
     pub actors: Vec<String>,
+    // Sutradara dan pemeran beserta link halaman mereka di situs sumber
+    #[serde(default)]
+    pub directors: Vec<Person>,
+    #[serde(default)]
+    pub cast: Vec<Person>,
     pub url: String,
     pub watch_url: Option<String>,
     pub related_movies: Vec<Movie>,
+    // ID provider sumber data
+    #[serde(default)]
+    pub provider: String,
+    // Rasio field yang berhasil di-parse dari halaman detail (0.0 - 1.0)
+    pub completeness: f32,
+    // Field yang tidak ditemukan saat parsing, hanya dikirim ke client jika ?debug=true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_warnings: Option<Vec<ParseWarning>>,
+}
+
+// Pemeran atau sutradara, ID diambil dari slug URL halaman /cast/{id}/ atau /director/{id}/
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Person {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+}
+
+// Peringatan untuk field halaman detail yang diharapkan tapi tidak ditemukan
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParseWarning {
+    pub field: String,
+    pub message: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,6 +181,9 @@ pub struct Country {
     pub id: String,
     pub name: String,
     pub url: String,
+    // Jumlah film, hanya tersedia jika daftar diambil dari REST API WordPress
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -85,4 +191,106 @@ pub struct CountryResponse {
     pub status: String,
     pub message: String,
     pub data: Vec<Country>,
-} 
\ No newline at end of file
+} 
+
+// Term taksonomi WordPress (genre, negara, tahun, kualitas, pemeran, sutradara)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxonomyTerm {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TermResponse {
+    pub status: String,
+    pub message: String,
+    pub data: Vec<TaxonomyTerm>,
+}
+
+impl From<TaxonomyTerm> for Genre {
+    fn from(term: TaxonomyTerm) -> Self {
+        Genre {
+            id: term.id,
+            name: term.name,
+            url: term.url,
+            count: term.count,
+        }
+    }
+}
+
+impl From<TaxonomyTerm> for Country {
+    fn from(term: TaxonomyTerm) -> Self {
+        Country {
+            id: term.id,
+            name: term.name,
+            url: term.url,
+            count: term.count,
+        }
+    }
+}
+
+// Satu film di katalog lengkap situs. Judul dan waktu perubahan tidak tersedia
+// jika katalog diambil dari sitemap.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CatalogEntry {
+    pub id: String,
+    pub title: Option<String>,
+    pub url: String,
+    pub modified: Option<String>,
+    #[serde(default)]
+    pub provider: String,
+}
+
+// Satu halaman katalog beserta sumber datanya ("rest", "sitemap" atau "html")
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CatalogPage {
+    pub page: u32,
+    pub per_page: u32,
+    pub total_items: Option<u32>,
+    pub total_pages: Option<u32>,
+    pub source: String,
+    pub entries: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CatalogResponse {
+    pub status: String,
+    pub message: String,
+    pub source: String,
+    pub pagination: PaginationInfo,
+    pub data: Vec<CatalogEntry>,
+}
+
+// Daftar film seseorang dari halaman pemeran dan sutradara
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PersonMovies {
+    pub person: Person,
+    // Peran yang ditemukan: "cast" dan/atau "director"
+    pub roles: Vec<String>,
+    pub movies: Vec<Movie>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersonResponse {
+    pub status: String,
+    pub message: String,
+    pub pagination: PaginationInfo,
+    pub data: PersonMovies,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscoverResponse {
+    pub status: String,
+    pub message: String,
+    pub pagination: PaginationInfo,
+    // "advanced_search" jika memakai pencarian lanjutan upstream, "listing" jika memfilter halaman listing
+    pub strategy: String,
+    // true jika batas halaman upstream tercapai sehingga hasil mungkin belum lengkap
+    pub partial: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub data: Vec<Movie>,
+}
@@ -0,0 +1,280 @@
+use crate::config::{CANARY_INTERVAL_SECS, CANARY_MIN_ITEMS, CANARY_MOVIE_ID};
+use crate::errors::AppError;
+use crate::models::movie::{Movie, MovieDetail};
+use crate::scraper::filmapik::{run_blocking, FilmapikScraper, UpstreamPage};
+use crate::scraper::{parser, snapshot};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::RwLock;
+use std::time::Duration;
+
+// Hasil pemeriksaan satu field
+#[derive(Debug, Serialize, Clone)]
+pub struct FieldCheck {
+    pub page: &'static str,
+    pub field: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+// Informasi kegagalan terakhir beserta lokasi snapshot HTML-nya
+#[derive(Debug, Serialize, Clone)]
+pub struct CanaryFailure {
+    pub checked_at: DateTime<Utc>,
+    pub url: String,
+    pub failed_checks: Vec<String>,
+    pub error: Option<String>,
+    pub snapshot_id: Option<String>,
+    pub snapshot_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Unknown,
+    Healthy,
+    Unhealthy,
+}
+
+// Hasil pemeriksaan kesehatan scraper terakhir
+#[derive(Debug, Serialize, Clone)]
+pub struct ScraperHealth {
+    pub status: HealthStatus,
+    pub checked_at: Option<DateTime<Utc>>,
+    pub interval_secs: u64,
+    pub checks: Vec<FieldCheck>,
+    pub last_failure: Option<CanaryFailure>,
+}
+
+lazy_static! {
+    static ref HEALTH: RwLock<ScraperHealth> = RwLock::new(ScraperHealth {
+        status: HealthStatus::Unknown,
+        checked_at: None,
+        interval_secs: *CANARY_INTERVAL_SECS,
+        checks: Vec::new(),
+        last_failure: None,
+    });
+}
+
+fn check(page: &'static str, field: &'static str, passed: bool, detail: String) -> FieldCheck {
+    FieldCheck { page, field, passed, detail }
+}
+
+// Ekspektasi untuk halaman listing: jumlah film minimum, judul dan poster selalu ada, tahun terbaca di sebagian besar film
+pub fn check_listing(movies: &[Movie], min_items: usize) -> Vec<FieldCheck> {
+    let total = movies.len();
+    let with_title = movies.iter().filter(|m| !m.title.is_empty()).count();
+    let with_poster = movies.iter().filter(|m| !m.poster.is_empty()).count();
+    let with_year = movies.iter().filter(|m| m.year.is_some()).count();
+
+    vec![
+        check("listing", "items", total >= min_items, format!("{} film (minimum {})", total, min_items)),
+        check("listing", "title", total > 0 && with_title == total, format!("{}/{}", with_title, total)),
+        check("listing", "poster", total > 0 && with_poster == total, format!("{}/{}", with_poster, total)),
+        check("listing", "year", total > 0 && with_year * 2 >= total, format!("{}/{}", with_year, total)),
+    ]
+}
+
+// Ekspektasi untuk halaman detail: judul, poster, tahun, dan minimal satu stream
+pub fn check_detail(detail: &MovieDetail) -> Vec<FieldCheck> {
+    let has_title = !detail.title.is_empty() && detail.title != "Unknown Title";
+
+    vec![
+        check("detail", "title", has_title, detail.title.clone()),
+        check("detail", "poster", !detail.poster.is_empty(), detail.poster.clone()),
+        check("detail", "year", detail.year.is_some(), format!("{:?}", detail.year)),
+        check("detail", "stream", detail.watch_url.is_some(), detail.watch_url.clone().unwrap_or_default()),
+    ]
+}
+
+// Hasil pemeriksaan satu halaman
+struct PageOutcome {
+    url: String,
+    checks: Vec<FieldCheck>,
+    error: Option<String>,
+    page: Option<UpstreamPage>,
+}
+
+impl PageOutcome {
+    fn failed(&self) -> bool {
+        self.error.is_some() || self.checks.iter().any(|c| !c.passed)
+    }
+}
+
+async fn run_listing(scraper: &FilmapikScraper) -> (PageOutcome, Option<String>) {
+    let base = scraper.base_url();
+    let url = format!("{}/", base);
+
+    let page = match scraper.fetch_page(&url, base).await {
+        Ok(page) => page,
+        Err(e) => return (PageOutcome { url, checks: Vec::new(), error: Some(e.to_string()), page: None }, None),
+    };
+
+    match run_blocking(move || (parser::parse_movie_list(&page.body), page)).await {
+        Ok((listing, page)) => {
+            let first_id = listing.movies.first().map(|m| m.id.clone());
+            let checks = check_listing(&listing.movies, *CANARY_MIN_ITEMS);
+            (PageOutcome { url, checks, error: None, page: Some(page) }, first_id)
+        },
+        Err(e) => (PageOutcome { url, checks: Vec::new(), error: Some(e.to_string()), page: None }, None),
+    }
+}
+
+async fn run_detail(scraper: &FilmapikScraper, movie_id: &str) -> PageOutcome {
+    let base = scraper.base_url();
+    let url = format!("{}/{}/", base, movie_id);
+
+    let page = match scraper.fetch_page(&url, format!("{}/", base)).await {
+        Ok(page) => page,
+        Err(e) => return PageOutcome { url, checks: Vec::new(), error: Some(e.to_string()), page: None },
+    };
+
+    let parse_id = movie_id.to_string();
+    let parsed: Result<(MovieDetail, UpstreamPage), AppError> = run_blocking(move || {
+        let detail = parser::parse_movie_detail(&page.body, &parse_id, &page.url);
+        (detail, page)
+    }).await;
+
+    match parsed {
+        Ok((detail, page)) => PageOutcome { url, checks: check_detail(&detail), error: None, page: Some(page) },
+        Err(e) => PageOutcome { url, checks: Vec::new(), error: Some(e.to_string()), page: None },
+    }
+}
+
+// Menjalankan satu kali pemeriksaan. Request ke upstream selalu dikirim langsung tanpa cache.
+pub async fn run_check(scraper: &FilmapikScraper) -> ScraperHealth {
+    info!("Menjalankan pemeriksaan kesehatan scraper");
+
+    let (listing, first_id) = run_listing(scraper).await;
+    let mut outcomes = vec![("listing", listing)];
+
+    match CANARY_MOVIE_ID.clone().or(first_id) {
+        Some(movie_id) => outcomes.push(("detail", run_detail(scraper, &movie_id).await)),
+        None => outcomes.push((
+            "detail",
+            PageOutcome {
+                url: String::new(),
+                checks: Vec::new(),
+                error: Some("Tidak ada film untuk memeriksa halaman detail".to_string()),
+                page: None,
+            },
+        )),
+    }
+
+    let checked_at = Utc::now();
+    let mut checks = Vec::new();
+    let mut failure = None;
+
+    for (kind, outcome) in outcomes {
+        if outcome.failed() && failure.is_none() {
+            let failed_checks: Vec<String> = outcome
+                .checks
+                .iter()
+                .filter(|c| !c.passed)
+                .map(|c| format!("{}.{}", c.page, c.field))
+                .collect();
+            warn!(
+                "Pemeriksaan scraper gagal untuk {}: {:?} {}",
+                outcome.url,
+                failed_checks,
+                outcome.error.as_deref().unwrap_or_default()
+            );
+
+            // HTML halaman yang gagal disimpan ke direktori snapshot agar bisa diperiksa dan dipakai sebagai fixture
+            let reason = format!("pemeriksaan kesehatan gagal: {:?}", failed_checks);
+            let snapshot = match outcome.page.clone() {
+                Some(page) => run_blocking(move || snapshot::capture(&page, kind, &reason)).await.ok().flatten(),
+                None => None,
+            };
+
+            failure = Some(CanaryFailure {
+                checked_at,
+                url: outcome.url.clone(),
+                failed_checks,
+                error: outcome.error.clone(),
+                snapshot_id: snapshot.as_ref().map(|meta| meta.id.clone()),
+                snapshot_path: snapshot.map(|meta| snapshot::html_path(&meta.id).to_string_lossy().into_owned()),
+            });
+        }
+        checks.extend(outcome.checks);
+    }
+
+    let mut health = HEALTH.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    health.status = if failure.is_some() { HealthStatus::Unhealthy } else { HealthStatus::Healthy };
+    health.checked_at = Some(checked_at);
+    health.checks = checks;
+    if failure.is_some() {
+        health.last_failure = failure;
+    }
+
+    health.clone()
+}
+
+// Hasil pemeriksaan terakhir
+pub fn current() -> ScraperHealth {
+    HEALTH
+        .read()
+        .map(|health| health.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().clone())
+}
+
+// Menjalankan pemeriksaan secara berkala di background
+pub fn spawn(scraper: FilmapikScraper) {
+    let interval_secs = *CANARY_INTERVAL_SECS;
+    if interval_secs == 0 {
+        info!("Pemeriksaan kesehatan scraper dinonaktifkan");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            run_check(&scraper).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(title: &str, poster: &str, year: Option<i32>) -> Movie {
+        Movie {
+            id: "film".to_string(),
+            title: title.to_string(),
+            poster: poster.to_string(),
+            year,
+            rating: None,
+            quality: None,
+            genres: Vec::new(),
+            url: String::new(),
+            provider: String::new(),
+            views: None,
+            description: None,
+            duration: None,
+            country: None,
+            director: None,
+            expanded: None,
+        }
+    }
+
+    #[test]
+    fn listing_checks_pass_for_complete_items() {
+        let movies = vec![movie("A (2023)", "a.jpg", Some(2023)), movie("B", "b.jpg", None)];
+        assert!(check_listing(&movies, 2).iter().all(|c| c.passed));
+    }
+
+    #[test]
+    fn listing_checks_report_failing_fields() {
+        let movies = vec![movie("A", "", None), movie("B", "b.jpg", None)];
+        let failed: Vec<_> = check_listing(&movies, 10)
+            .into_iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.field)
+            .collect();
+        assert_eq!(failed, vec!["items", "poster", "year"]);
+    }
+}
@@ -0,0 +1,42 @@
+use reqwest::StatusCode;
+
+// Penanda halaman challenge anti-bot (Cloudflare, DDoS-Guard) yang sering disajikan dengan status 200
+const CHALLENGE_MARKERS: &[&str] = &[
+    "cf-browser-verification",
+    "cf_chl_opt",
+    "/cdn-cgi/challenge-platform/",
+    "cf-challenge-running",
+    "<title>Just a moment...</title>",
+    "<title>Attention Required! | Cloudflare</title>",
+    "Checking your browser before accessing",
+    "ddos-guard",
+];
+
+// Penanda captcha. Widget captcha juga bisa muncul di halaman normal (misalnya form komentar),
+// jadi hanya dianggap challenge jika halaman tidak memiliki struktur tema situs.
+const CAPTCHA_MARKERS: &[&str] = &["g-recaptcha", "h-captcha", "cf-turnstile"];
+
+// Penanda bahwa halaman dirender oleh tema situs FilmApik (Muvipro)
+const SITE_LAYOUT_MARKER: &str = "gmr-";
+
+// Mendeteksi apakah response upstream merupakan halaman challenge/interstitial.
+// Mengembalikan penanda yang ditemukan jika halaman terdeteksi sebagai challenge.
+pub fn detect_challenge(status: StatusCode, server: Option<&str>, body: &str) -> Option<String> {
+    if let Some(marker) = CHALLENGE_MARKERS.iter().find(|marker| body.contains(*marker)) {
+        return Some(marker.to_string());
+    }
+
+    if !body.contains(SITE_LAYOUT_MARKER) {
+        if let Some(marker) = CAPTCHA_MARKERS.iter().find(|marker| body.contains(*marker)) {
+            return Some(marker.to_string());
+        }
+    }
+
+    // Cloudflare mengembalikan 403/503 dengan header server "cloudflare" saat memblokir request
+    let from_cloudflare = server.is_some_and(|s| s.eq_ignore_ascii_case("cloudflare"));
+    if from_cloudflare && (status == StatusCode::FORBIDDEN || status == StatusCode::SERVICE_UNAVAILABLE) {
+        return Some(format!("cloudflare {}", status.as_u16()));
+    }
+
+    None
+}
@@ -0,0 +1,392 @@
+use crate::config::DISCOVER_MAX_PAGES;
+use crate::errors::AppError;
+use crate::models::movie::Movie;
+use crate::scraper::filmapik::FilmapikScraper;
+use crate::scraper::listing::{ListingSort, SortField};
+use futures::future::join_all;
+use log::{info, warn};
+use std::collections::HashSet;
+
+// Jumlah hasil per halaman discover
+pub const DISCOVER_PER_PAGE: usize = 24;
+
+// Jumlah halaman upstream yang diambil bersamaan
+const DISCOVER_CONCURRENCY: usize = 4;
+
+// Filter discover, semua facet opsional dan digabung dengan AND
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscoverFilters {
+    pub genre: Option<String>,
+    pub country: Option<String>,
+    pub year: Option<i32>,
+    pub quality: Option<String>,
+    pub min_rating: Option<f32>,
+}
+
+impl DiscoverFilters {
+    // Jumlah facet yang memiliki halaman listing di situs (genre, negara, tahun, kualitas)
+    fn facet_count(&self) -> usize {
+        [self.genre.is_some(), self.country.is_some(), self.year.is_some(), self.quality.is_some()]
+            .iter()
+            .filter(|set| **set)
+            .count()
+    }
+
+    // Apakah ada facet yang bisa diperiksa dari item listing (genre, tahun, kualitas)
+    fn has_checkable_facet(&self) -> bool {
+        self.genre.is_some() || self.year.is_some() || self.quality.is_some()
+    }
+
+    // Apakah item hasil pencarian lanjutan tidak bertentangan dengan facet, dipakai untuk memastikan
+    // upstream benar-benar memfilter hasil (nilai yang tidak diketahui tidak dianggap bertentangan)
+    fn consistent_with(&self, movie: &Movie) -> bool {
+        self.genre.as_ref().is_none_or(|genre| movie.genres.iter().any(|g| slugify(g) == *genre))
+            && self.year.is_none_or(|year| movie.year.is_none_or(|y| y == year))
+            && self.quality.as_ref().is_none_or(|quality| movie.quality.as_deref().is_none_or(|q| slugify(q) == *quality))
+    }
+
+    // Apakah film memenuhi semua filter yang bisa diperiksa dari item listing.
+    // Negara tidak ada di item listing, facet yang menjadi sumber listing tidak diperiksa ulang.
+    fn matches(&self, movie: &Movie, source: &Source) -> bool {
+        if let Some(genre) = &self.genre {
+            if !matches!(source, Source::Genre(_)) && !movie.genres.iter().any(|g| slugify(g) == *genre) {
+                return false;
+            }
+        }
+        if let Some(year) = self.year {
+            if !matches!(source, Source::Year(_)) && movie.year != Some(year) {
+                return false;
+            }
+        }
+        if let Some(quality) = &self.quality {
+            if !matches!(source, Source::Quality(_)) && movie.quality.as_deref().map(slugify).as_ref() != Some(quality) {
+                return false;
+            }
+        }
+        if let Some(min_rating) = self.min_rating {
+            if !movie.rating.is_some_and(|rating| rating >= min_rating) {
+                return false;
+            }
+        }
+        true
+    }
+
+    // Query string pencarian lanjutan tema Muvipro
+    fn advanced_query(&self) -> String {
+        let param = |value: Option<String>| {
+            value
+                .map(|v| url::form_urlencoded::byte_serialize(v.as_bytes()).collect::<String>())
+                .unwrap_or_default()
+        };
+        format!(
+            "s=&search=advanced&post_type=&index=&orderby=&genre={}&movieyear={}&country={}&quality={}",
+            param(self.genre.clone()),
+            param(self.year.map(|y| y.to_string())),
+            param(self.country.clone()),
+            param(self.quality.clone()),
+        )
+    }
+}
+
+// Urutan hasil discover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiscoverSort {
+    // Urutan upstream (terbaru lebih dulu)
+    #[default]
+    Latest,
+    Rating,
+    Year,
+    Title,
+}
+
+impl DiscoverSort {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "latest" => Ok(DiscoverSort::Latest),
+            "rating" => Ok(DiscoverSort::Rating),
+            "year" => Ok(DiscoverSort::Year),
+            "title" => Ok(DiscoverSort::Title),
+            other => Err(format!("Urutan tidak dikenal: {} (pilihan: latest, rating, year, title)", other)),
+        }
+    }
+
+    fn sort(self, movies: &mut [Movie]) {
+        let field = match self {
+            DiscoverSort::Latest => return,
+            DiscoverSort::Rating => SortField::Rating,
+            DiscoverSort::Year => SortField::Year,
+            DiscoverSort::Title => SortField::Title,
+        };
+        ListingSort::new(field, None).sort(movies);
+    }
+}
+
+// Listing upstream yang dipakai sebagai sumber kandidat
+#[derive(Debug, Clone, PartialEq)]
+enum Source {
+    Advanced(String),
+    Country(String),
+    Year(i32),
+    Quality(String),
+    Genre(String),
+    Latest,
+}
+
+impl Source {
+    fn name(&self) -> &'static str {
+        match self {
+            Source::Advanced(_) => "advanced_search",
+            _ => "listing",
+        }
+    }
+
+    async fn load(&self, scraper: &FilmapikScraper, page: u32) -> Result<Vec<Movie>, AppError> {
+        match self {
+            Source::Advanced(query) => scraper.get_advanced_search(query, page).await,
+            Source::Country(country) => scraper.get_movies_by_country(country, page).await,
+            Source::Year(year) => scraper.get_movies_by_year(*year, page).await,
+            Source::Quality(quality) => scraper.get_movies_by_quality(quality, page).await,
+            Source::Genre(genre) => scraper.get_movies_by_genre(genre, page).await,
+            Source::Latest => scraper.get_latest_movies(page).await,
+        }
+    }
+}
+
+// Hasil discover untuk satu halaman
+#[derive(Debug, Clone)]
+pub struct DiscoverResult {
+    pub movies: Vec<Movie>,
+    pub page: u32,
+    pub per_page: u32,
+    // Hanya diketahui jika seluruh listing sumber sudah dipindai
+    pub total_items: Option<u32>,
+    pub total_pages: Option<u32>,
+    // true jika batas halaman upstream tercapai sebelum hasil lengkap
+    pub partial: bool,
+    pub note: Option<String>,
+    // "advanced_search" atau "listing"
+    pub strategy: String,
+    pub upstream_pages: u32,
+}
+
+// Sumber listing jika pencarian lanjutan tidak dipakai: negara harus menjadi sumber karena tidak bisa
+// diperiksa dari item listing, lalu facet yang biasanya paling sedikit hasilnya
+fn listing_source(filters: &DiscoverFilters) -> Source {
+    if let Some(country) = &filters.country {
+        Source::Country(country.clone())
+    } else if let Some(year) = filters.year {
+        Source::Year(year)
+    } else if let Some(quality) = &filters.quality {
+        Source::Quality(quality.clone())
+    } else if let Some(genre) = &filters.genre {
+        Source::Genre(genre.clone())
+    } else {
+        Source::Latest
+    }
+}
+
+pub async fn discover(
+    scraper: &FilmapikScraper,
+    filters: &DiscoverFilters,
+    sort: DiscoverSort,
+    page: u32,
+) -> Result<DiscoverResult, AppError> {
+    let budget = *DISCOVER_MAX_PAGES;
+    let page_start = (page.max(1) as usize - 1) * DISCOVER_PER_PAGE;
+    // Urutan upstream cukup dipindai sampai halaman yang diminta (+1 untuk mengetahui ada halaman berikutnya),
+    // urutan lain membutuhkan seluruh hasil dalam batas agar setiap halaman konsisten
+    let needed = match sort {
+        DiscoverSort::Latest => page_start + DISCOVER_PER_PAGE + 1,
+        _ => usize::MAX,
+    };
+
+    let mut scan = Scan {
+        next_page: 1,
+        ..Default::default()
+    };
+    let mut source = listing_source(filters);
+
+    // Pencarian lanjutan upstream hanya dipercaya jika hasilnya benar-benar difilter.
+    // Filter dengan satu facet langsung memakai halaman listing facet tersebut.
+    if filters.facet_count() >= 2 && filters.has_checkable_facet() {
+        let advanced = Source::Advanced(filters.advanced_query());
+        scan.fetched += 1;
+        match advanced.load(scraper, 1).await {
+            Ok(movies) if !movies.is_empty() && movies.iter().all(|m| filters.consistent_with(m)) => {
+                info!("Pencarian lanjutan {} dipakai untuk discover", scraper.site().name);
+                scan.add(movies, filters, &advanced);
+                scan.next_page = 2;
+                source = advanced;
+            },
+            Ok(_) => info!("Pencarian lanjutan {} tidak memfilter hasil, memakai halaman listing", scraper.site().name),
+            Err(e) => warn!("Pencarian lanjutan {} gagal: {}, memakai halaman listing", scraper.site().name, e),
+        }
+    }
+
+    while !scan.exhausted && scan.fetched < budget && scan.matches.len() < needed {
+        let batch: Vec<u32> = (scan.next_page..)
+            .take(DISCOVER_CONCURRENCY.min((budget - scan.fetched) as usize))
+            .collect();
+        let results = join_all(batch.iter().map(|n| source.load(scraper, *n))).await;
+        scan.next_page += batch.len() as u32;
+
+        for result in results {
+            scan.fetched += 1;
+            match result {
+                Ok(movies) if !movies.is_empty() => scan.add(movies, filters, &source),
+                // Halaman setelah halaman terakhir mengembalikan 404 atau listing kosong
+                Ok(_) | Err(AppError::UpstreamNotFound { .. }) => {
+                    scan.exhausted = true;
+                    break;
+                },
+                Err(e) if scan.matches.is_empty() => return Err(e),
+                Err(e) => {
+                    warn!("Discover berhenti karena halaman upstream gagal: {}", e);
+                    scan.error = Some(e.to_string());
+                    break;
+                },
+            }
+        }
+        if scan.error.is_some() {
+            break;
+        }
+    }
+
+    let mut matches = scan.matches;
+    sort.sort(&mut matches);
+
+    let complete = scan.exhausted;
+    let partial = !complete && (sort != DiscoverSort::Latest || matches.len() <= page_start + DISCOVER_PER_PAGE);
+    let note = match (&scan.error, partial) {
+        (Some(error), _) => Some(format!("Hasil parsial: pengambilan halaman upstream gagal ({})", error)),
+        (None, true) => Some(format!(
+            "Hasil parsial: hanya {} halaman upstream pertama yang diperiksa",
+            scan.fetched
+        )),
+        (None, false) => None,
+    };
+
+    let total = matches.len();
+    Ok(DiscoverResult {
+        movies: matches.into_iter().skip(page_start).take(DISCOVER_PER_PAGE).collect(),
+        page,
+        per_page: DISCOVER_PER_PAGE as u32,
+        total_items: complete.then_some(total as u32),
+        total_pages: complete.then_some(total.div_ceil(DISCOVER_PER_PAGE) as u32),
+        partial: partial || scan.error.is_some(),
+        note,
+        strategy: source.name().to_string(),
+        upstream_pages: scan.fetched,
+    })
+}
+
+#[derive(Default)]
+struct Scan {
+    matches: Vec<Movie>,
+    seen: HashSet<String>,
+    fetched: u32,
+    next_page: u32,
+    exhausted: bool,
+    error: Option<String>,
+}
+
+impl Scan {
+    fn add(&mut self, movies: Vec<Movie>, filters: &DiscoverFilters, source: &Source) {
+        for movie in movies {
+            if filters.matches(&movie, source) && self.seen.insert(movie.id.clone()) {
+                self.matches.push(movie);
+            }
+        }
+    }
+}
+
+// "Science Fiction" -> "science-fiction", dipakai untuk membandingkan nama genre/kualitas dengan slug
+pub(crate) fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(id: &str, genres: &[&str], year: Option<i32>, quality: &str, rating: Option<f32>) -> Movie {
+        Movie {
+            id: id.to_string(),
+            title: id.to_string(),
+            poster: String::new(),
+            year,
+            rating,
+            quality: Some(quality.to_string()),
+            genres: genres.iter().map(|g| g.to_string()).collect(),
+            url: String::new(),
+            provider: String::new(),
+            views: None,
+            description: None,
+            duration: None,
+            country: None,
+            director: None,
+            expanded: None,
+        }
+    }
+
+    #[test]
+    fn filters_check_listing_fields_except_source_facet() {
+        let filters = DiscoverFilters {
+            genre: Some("science-fiction".to_string()),
+            year: Some(2023),
+            quality: Some("bluray".to_string()),
+            min_rating: Some(7.0),
+            ..Default::default()
+        };
+        let good = movie("a", &["Action", "Science Fiction"], Some(2023), "BluRay", Some(7.5));
+        assert!(filters.matches(&good, &Source::Latest));
+
+        let wrong_year = movie("b", &["Science Fiction"], Some(2022), "BluRay", Some(8.0));
+        assert!(!filters.matches(&wrong_year, &Source::Latest));
+        // Tahun tidak diperiksa ulang jika sumbernya halaman tahun
+        assert!(filters.matches(&wrong_year, &Source::Year(2023)));
+
+        let low_rating = movie("c", &["Science Fiction"], Some(2023), "BluRay", Some(6.9));
+        assert!(!filters.matches(&low_rating, &Source::Latest));
+        let no_rating = movie("d", &["Science Fiction"], Some(2023), "BluRay", None);
+        assert!(!filters.matches(&no_rating, &Source::Latest));
+
+        // Verifikasi pencarian lanjutan mengabaikan rating dan nilai yang tidak diketahui
+        assert!(filters.consistent_with(&movie("e", &["Science Fiction"], None, "BluRay", None)));
+        assert!(!filters.consistent_with(&wrong_year));
+    }
+
+    #[test]
+    fn country_is_always_the_listing_source() {
+        let filters = DiscoverFilters {
+            genre: Some("horror".to_string()),
+            country: Some("korea".to_string()),
+            year: Some(2023),
+            ..Default::default()
+        };
+        assert_eq!(listing_source(&filters), Source::Country("korea".to_string()));
+        assert_eq!(listing_source(&DiscoverFilters::default()), Source::Latest);
+        assert!(filters.advanced_query().contains("genre=horror&movieyear=2023&country=korea&quality="));
+    }
+
+    #[test]
+    fn sorts_and_parses_sort_values() {
+        assert_eq!(DiscoverSort::parse("Rating").unwrap(), DiscoverSort::Rating);
+        assert_eq!(DiscoverSort::parse("").unwrap(), DiscoverSort::Latest);
+        assert!(DiscoverSort::parse("random").is_err());
+
+        let mut movies = vec![
+            movie("a", &[], Some(2020), "HD", Some(6.0)),
+            movie("b", &[], Some(2023), "HD", None),
+            movie("c", &[], Some(2021), "HD", Some(8.0)),
+        ];
+        DiscoverSort::Rating.sort(&mut movies);
+        assert_eq!(movies.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(), vec!["c", "a", "b"]);
+        DiscoverSort::Year.sort(&mut movies);
+        assert_eq!(movies.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(), vec!["b", "c", "a"]);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::config::{self, DOMAIN_MOVE_THRESHOLD};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::Mutex;
+use url::Url;
+
+// Jumlah maksimum event perpindahan domain yang disimpan di memori
+const MAX_DOMAIN_EVENTS: usize = 20;
+
+// Event yang dicatat setiap kali base URL FilmApik berpindah
+#[derive(Debug, Serialize, Clone)]
+pub struct DomainChangeEvent {
+    pub from: String,
+    pub to: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct DomainTracker {
+    // Origin tujuan redirect yang sedang diamati beserta jumlah kemunculannya
+    candidate: Option<String>,
+    hits: u32,
+    events: Vec<DomainChangeEvent>,
+}
+
+lazy_static! {
+    static ref TRACKER: Mutex<DomainTracker> = Mutex::new(DomainTracker::default());
+}
+
+// Mengambil origin (scheme://host[:port]) dari sebuah URL
+fn origin_of(url: &Url) -> Option<String> {
+    match url.origin() {
+        origin @ url::Origin::Tuple(..) => Some(origin.ascii_serialization()),
+        url::Origin::Opaque(_) => None,
+    }
+}
+
+// Mencatat URL akhir dari response upstream yang sukses.
+// Jika upstream terus mengarahkan ke domain lain sebanyak DOMAIN_MOVE_THRESHOLD kali berturut-turut,
+// base URL aktif diganti ke domain baru tersebut.
+pub fn observe_response(requested_url: &str, final_url: &Url) {
+    let Ok(requested) = Url::parse(requested_url) else {
+        return;
+    };
+    let (Some(requested_origin), Some(final_origin)) = (origin_of(&requested), origin_of(final_url)) else {
+        return;
+    };
+
+    let active_url = config::filmapik_url();
+    let Some(active_origin) = Url::parse(&active_url).ok().as_ref().and_then(origin_of) else {
+        return;
+    };
+
+    // Abaikan request yang bukan ke base URL aktif (misalnya request lama sebelum domain berpindah)
+    if requested_origin != active_origin {
+        return;
+    }
+
+    let mut tracker = TRACKER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if final_origin == active_origin {
+        tracker.candidate = None;
+        tracker.hits = 0;
+        return;
+    }
+
+    if tracker.candidate.as_deref() == Some(final_origin.as_str()) {
+        tracker.hits += 1;
+    } else {
+        info!("Upstream mengarahkan {} ke domain {}", requested_url, final_origin);
+        tracker.candidate = Some(final_origin.clone());
+        tracker.hits = 1;
+    }
+
+    if tracker.hits < *DOMAIN_MOVE_THRESHOLD {
+        return;
+    }
+
+    warn!(
+        "Domain FilmApik berpindah dari {} ke {} (terdeteksi dari {} redirect berturut-turut)",
+        active_url, final_origin, tracker.hits
+    );
+    config::set_filmapik_url(&final_origin);
+
+    tracker.events.push(DomainChangeEvent {
+        from: active_url,
+        to: final_origin,
+        detected_at: Utc::now(),
+    });
+    if tracker.events.len() > MAX_DOMAIN_EVENTS {
+        tracker.events.remove(0);
+    }
+    tracker.candidate = None;
+    tracker.hits = 0;
+}
+
+// Mengambil riwayat perpindahan domain (terbaru di akhir)
+pub fn domain_events() -> Vec<DomainChangeEvent> {
+    TRACKER
+        .lock()
+        .map(|tracker| tracker.events.clone())
+        .unwrap_or_default()
+}
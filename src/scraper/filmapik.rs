@@ -1,11 +1,17 @@
-use crate::config::FILMAPIK_URL;
+use crate::config::filmapik_url;
 use crate::errors::AppError;
-use crate::models::movie::{Movie, Genre, MovieDetail, Country};
+use crate::metrics::METRICS;
+use crate::models::movie::{Movie, Genre, MovieDetail, Country, CatalogEntry, CatalogPage, Person, PersonMovies, TaxonomyTerm};
+use crate::scraper::wordpress::{self, CatalogSource, Taxonomy};
+use crate::scraper::listing::Listing;
+use crate::scraper::{challenge, domain, parser, snapshot, vcr};
 use anyhow::Result;
-use log::{error, info};
-use reqwest::{Client, header};
-use scraper::{Html, Selector};
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, Utc};
+use lazy_static::lazy_static;
+use log::{error, info, warn};
+use regex::Regex;
+use reqwest::{Client, StatusCode, header};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use moka::future::Cache;
 use std::sync::Arc;
@@ -15,19 +21,147 @@ const CACHE_TTL_MOVIES: u64 = 1800; // 30 menit untuk daftar film
 const CACHE_TTL_GENRES: u64 = 86400; // 24 jam untuk genre (jarang berubah)
 const CACHE_TTL_COUNTRIES: u64 = 86400; // 24 jam untuk negara (jarang berubah)
 const CACHE_TTL_DETAILS: u64 = 3600; // 1 jam untuk detail film
+const CACHE_TTL_NOT_FOUND: u64 = 300; // 5 menit untuk halaman yang tidak ditemukan (negative cache)
 const CACHE_MAX_CAPACITY: u64 = 1000; // Maksimum item dalam cache
 
+// Field halaman detail yang wajib ada, jika tidak ditemukan HTML-nya disimpan sebagai snapshot
+const DETAIL_CORE_FIELDS: [&str; 2] = ["title", "poster"];
+
+// Ringkasan peringatan parse ditulis ke log setiap sejumlah halaman detail
+const PARSE_WARNING_LOG_INTERVAL: u64 = 100;
+
+// Default Retry-After (detik) jika upstream mengembalikan 429 tanpa header Retry-After
+const DEFAULT_RETRY_AFTER: u64 = 60;
+
+// Daftar genre yang tersedia di FilmApik (secara statis)
+const GENRES: [(&str, &str); 17] = [
+    ("action", "Action"),
+    ("adventure", "Adventure"),
+    ("animation", "Animation"),
+    ("comedy", "Comedy"),
+    ("crime", "Crime"),
+    ("documentary", "Documentary"),
+    ("drama", "Drama"),
+    ("family", "Family"),
+    ("fantasy", "Fantasy"),
+    ("history", "History"),
+    ("horror", "Horror"),
+    ("music", "Music"),
+    ("mystery", "Mystery"),
+    ("romance", "Romance"),
+    ("science-fiction", "Science Fiction"),
+    ("thriller", "Thriller"),
+    ("war", "War"),
+];
+
+// Daftar negara yang tersedia di FilmApik (secara statis)
+// Data diambil dari film-terbaru.html dan ditambahkan negara lainnya yang populer
+const COUNTRIES: [(&str, &str); 24] = [
+    ("usa", "USA"),
+    ("india", "India"),
+    ("china", "China"),
+    ("korea", "Korea"),
+    ("philippines", "Philippines"),
+    // Tambahan negara-negara lainnya
+    ("japan", "Japan"),
+    ("thailand", "Thailand"),
+    ("indonesia", "Indonesia"),
+    ("malaysia", "Malaysia"),
+    ("france", "France"),
+    ("germany", "Germany"),
+    ("uk", "United Kingdom"),
+    ("italy", "Italy"),
+    ("spain", "Spain"),
+    ("russia", "Russia"),
+    ("australia", "Australia"),
+    ("canada", "Canada"),
+    ("brazil", "Brazil"),
+    ("mexico", "Mexico"),
+    ("taiwan", "Taiwan"),
+    ("hongkong", "Hong Kong"),
+    ("vietnam", "Vietnam"),
+    ("turkey", "Turkey"),
+    ("singapore", "Singapore"),
+];
+
+// Kualitas video yang dipakai FilmApik, slug sesuai URL /quality/{slug}/
+const QUALITIES: [(&str, &str); 4] = [
+    ("hd", "HD"),
+    ("cam", "CAM"),
+    ("webdl", "WEBDL"),
+    ("bluray", "BluRay"),
+];
+
+// Tahun rilis terlama pada daftar tahun statis
+pub const MIN_YEAR: i32 = 1950;
+
+lazy_static! {
+    // Halaman 404 WordPress yang disajikan dengan status 200 memiliki class "error404" pada <body>
+    static ref WP_NOT_FOUND_RE: Regex = Regex::new(r#"<body[^>]*class="[^"]*\berror404\b"#).unwrap();
+}
+
+// Halaman dari upstream beserta informasi request/response-nya
+#[derive(Debug, Clone)]
+pub struct UpstreamPage {
+    pub url: String,
+    pub final_url: String,
+    pub status: u16,
+    pub referer: String,
+    // Header response, nilai header yang muncul berulang digabung dengan ", "
+    pub headers: BTreeMap<String, String>,
+    pub fetched_at: DateTime<Utc>,
+    pub body: String,
+}
+
 // Definisikan key untuk cache
 type CacheKey = String;
 
+// ID provider untuk situs FilmApik sendiri
+pub const FILMAPIK_PROVIDER_ID: &str = "filmapik";
+
+// Situs yang dilayani scraper: FilmApik atau situs lain yang memakai tema WordPress yang sama
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Site {
+    pub id: String,
+    pub name: String,
+    // None berarti memakai base URL FilmApik yang aktif (bisa berpindah saat domain FilmApik berubah)
+    pub base_url: Option<String>,
+}
+
+impl Site {
+    pub fn filmapik() -> Self {
+        Site {
+            id: FILMAPIK_PROVIDER_ID.to_string(),
+            name: "FilmApik".to_string(),
+            base_url: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FilmapikScraper {
     client: Client,
+    site: Arc<Site>,
     // Cache untuk berbagai jenis data
     movies_cache: Arc<Cache<CacheKey, Vec<Movie>>>,
     genres_cache: Arc<Cache<CacheKey, Vec<Genre>>>,
     countries_cache: Arc<Cache<CacheKey, Vec<Country>>>,
     movie_detail_cache: Arc<Cache<CacheKey, MovieDetail>>,
+    terms_cache: Arc<Cache<CacheKey, Vec<TaxonomyTerm>>>,
+    catalog_cache: Arc<Cache<CacheKey, CatalogPage>>,
+    // Seluruh isi sitemap post, dipakai untuk membagi katalog per halaman jika REST API tidak tersedia
+    sitemap_cache: Arc<Cache<CacheKey, Arc<Vec<CatalogEntry>>>>,
+    // Negative cache untuk URL upstream yang mengembalikan 404/410
+    not_found_cache: Arc<Cache<CacheKey, StatusCode>>,
+    // Nomor halaman terakhir setiap listing dari navigasi pagination
+    last_page_cache: Arc<Cache<CacheKey, u32>>,
+    heading_cache: Arc<Cache<CacheKey, String>>,
+}
+
+impl Default for FilmapikScraper {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FilmapikScraper {
@@ -124,21 +258,137 @@ impl FilmapikScraper {
             .max_capacity(CACHE_MAX_CAPACITY)
             .time_to_live(Duration::from_secs(CACHE_TTL_DETAILS))
             .build();
+            
+        let terms_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY / 10)
+            .time_to_live(Duration::from_secs(CACHE_TTL_GENRES))
+            .build();
+            
+        let catalog_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY)
+            .time_to_live(Duration::from_secs(CACHE_TTL_MOVIES))
+            .build();
+            
+        let sitemap_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY / 100)
+            .time_to_live(Duration::from_secs(CACHE_TTL_MOVIES))
+            .build();
+            
+        let not_found_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY)
+            .time_to_live(Duration::from_secs(CACHE_TTL_NOT_FOUND))
+            .build();
+            
+        let last_page_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY / 10)
+            .time_to_live(Duration::from_secs(CACHE_TTL_MOVIES))
+            .build();
+            
+        // Kapasitas dan TTL sama dengan cache film agar judul tidak hilang lebih dulu dari listing-nya
+        let heading_cache = Cache::builder()
+            .max_capacity(CACHE_MAX_CAPACITY)
+            .time_to_live(Duration::from_secs(CACHE_TTL_MOVIES))
+            .build();
         
         Self { 
             client,
+            site: Arc::new(Site::filmapik()),
             movies_cache: Arc::new(movies_cache),
             genres_cache: Arc::new(genres_cache),
             countries_cache: Arc::new(countries_cache),
             movie_detail_cache: Arc::new(movie_detail_cache),
+            terms_cache: Arc::new(terms_cache),
+            catalog_cache: Arc::new(catalog_cache),
+            sitemap_cache: Arc::new(sitemap_cache),
+            not_found_cache: Arc::new(not_found_cache),
+            last_page_cache: Arc::new(last_page_cache),
+            heading_cache: Arc::new(heading_cache),
         }
     }
 
+    // Scraper untuk situs lain dengan tema yang sama. Client dan cache dipakai bersama,
+    // key cache dibedakan dengan ID situs.
+    pub fn with_site(&self, site: Site) -> Self {
+        Self {
+            site: Arc::new(site),
+            ..self.clone()
+        }
+    }
+
+    pub fn site(&self) -> &Site {
+        &self.site
+    }
+
+    // Base URL situs tanpa trailing slash
+    pub fn base_url(&self) -> String {
+        match &self.site.base_url {
+            Some(url) => url.clone(),
+            None => filmapik_url(),
+        }
+    }
+
+    // Key cache diawali ID situs agar data dari situs yang berbeda tidak tertukar
+    fn cache_key(&self, key: &str) -> CacheKey {
+        format!("{}:{}", self.site.id, key)
+    }
+
+    // Key cache untuk daftar yang berisi URL absolut situs (genre, negara, term). Base URL ikut menjadi
+    // bagian key agar setelah perpindahan domain (scraper::domain) daftar dengan host lama tidak dipakai lagi.
+    fn site_cache_key(&self, key: &str) -> CacheKey {
+        self.cache_key(&format!("{}@{}", key, self.base_url()))
+    }
+
+    // Menandai film hasil parse dengan ID provider
+    fn tag_movies(&self, movies: &mut [Movie]) {
+        for movie in movies {
+            movie.provider = self.site.id.clone();
+        }
+    }
+
+    // Mengisi jumlah penonton dari detail film yang sudah ada di cache tanpa request ke upstream
+    pub(crate) async fn fill_known_views(&self, movies: &mut [Movie]) {
+        for movie in movies {
+            let cache_key = self.cache_key(&format!("movie_detail_{}", movie.id));
+            if let Some(detail) = self.movie_detail_cache.get(&cache_key).await {
+                movie.views = detail.views;
+            }
+        }
+    }
+
+    // Jumlah halaman listing dari navigasi pagination halaman pertama (lewat cache).
+    // None jika situs tidak menampilkan navigasi atau listing tidak memakai parse_listing (pencarian).
+    pub(crate) async fn last_page(&self, listing: &Listing) -> Result<Option<u32>, AppError> {
+        let listing_key = match listing {
+            Listing::Latest => "latest_movies".to_string(),
+            Listing::Popular => "popular_movies".to_string(),
+            Listing::Genre(genre) => format!("genre_{}", genre),
+            Listing::Country(country) => format!("country_{}", country),
+            Listing::Year(year) => format!("{}_{}", Taxonomy::Year.path(), year),
+            Listing::Quality(quality) => format!("{}_{}", Taxonomy::Quality.path(), quality.to_ascii_lowercase()),
+            Listing::Search(_) => return Ok(None),
+        };
+        let cache_key = self.cache_key(&format!("{}_last_page", listing_key));
+        
+        if let Some(last_page) = self.last_page_cache.get(&cache_key).await {
+            return Ok(Some(last_page));
+        }
+        
+        // Halaman pertama mencatat nomor halaman terakhir saat di-parse. Jika halaman itu sudah ada di cache
+        // film tetapi nomor halamannya sudah kedaluwarsa, halaman pertama diambil ulang dari upstream.
+        listing.load(self, 1).await?;
+        if self.last_page_cache.get(&cache_key).await.is_none() {
+            self.movies_cache.invalidate(&self.cache_key(&format!("{}_page_1", listing_key))).await;
+            listing.load(self, 1).await?;
+        }
+        
+        Ok(self.last_page_cache.get(&cache_key).await)
+    }
+
     pub async fn get_latest_movies(&self, page: u32) -> Result<Vec<Movie>, AppError> {
-        info!("Mengambil daftar film terbaru dari FilmApik - Halaman {}", page);
+        info!("Mengambil daftar film terbaru dari {} - Halaman {}", self.site.name, page);
         
         // Buat cache key berdasarkan tipe request dan halaman
-        let cache_key = format!("latest_movies_page_{}", page);
+        let cache_key = self.cache_key(&format!("latest_movies_page_{}", page));
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
@@ -148,43 +398,31 @@ impl FilmapikScraper {
         
         // Jika tidak ada di cache, ambil dari website
         // Ambil HTML dari website dengan parameter page
+        let base = self.base_url();
         let url = if page > 1 {
-            format!("{}/page/{}/", *FILMAPIK_URL, page)
+            format!("{}/page/{}/", base, page)
         } else {
-            format!("{}/", *FILMAPIK_URL)
+            format!("{}/", base)
         };
         
         // Tambahkan referer untuk setiap request yang berbeda
         // Referer biasanya URL sebelumnya (halaman sebelumnya atau homepage)
         let referer = if page > 1 {
-            format!("{}/page/{}/", *FILMAPIK_URL, page - 1)
+            format!("{}/page/{}/", base, page - 1)
         } else {
-            (*FILMAPIK_URL).to_string()
+            base.clone()
         };
         
         // Buat request dengan referer yang dinamis
-        let response = self.client.get(&url)
-            .header(header::REFERER, referer)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Gagal melakukan request ke {}: {}", url, e);
-                AppError::HttpError(e)
-            })?;
-        
-        let status = response.status();
-        if !status.is_success() {
-            error!("Server mengembalikan status error: {}", status);
-            return Err(AppError::ScrapingError(format!("Status HTTP error: {}", status)));
-        }
-        
-        let html = response.text().await.map_err(|e| {
-            error!("Gagal membaca response HTML: {}", e);
-            AppError::HttpError(e)
-        })?;
+        let upstream_page = self.fetch_page(&url, referer).await?;
         
         // Parse data film
-        let movies = self.parse_latest_movies(&html)?;
+        let movies = self.parse_listing(upstream_page, &cache_key).await?;
+        
+        // Daftar film terbaru/populer tidak pernah kosong, hasil kosong berarti upstream menyajikan halaman yang tidak semestinya
+        if movies.is_empty() {
+            return Err(Self::upstream_blocked(&url, "daftar film kosong"));
+        }
         
         // Simpan ke cache untuk penggunaan berikutnya
         self.movies_cache.insert(cache_key, movies.clone()).await;
@@ -192,112 +430,175 @@ impl FilmapikScraper {
         Ok(movies)
     }
 
-    fn parse_latest_movies(&self, html: &str) -> Result<Vec<Movie>, AppError> {
-        let document = Html::parse_document(html);
+    // Mengambil halaman dari upstream dan mencatat URL akhir untuk mendeteksi perpindahan domain
+    pub(crate) async fn fetch_page(&self, url: &str, referer: String) -> Result<UpstreamPage, AppError> {
+        // URL yang baru saja mengembalikan 404 tidak perlu diminta ulang ke upstream
+        if let Some(cached_status) = self.not_found_cache.get(url).await {
+            info!("Menggunakan negative cache untuk {}", url);
+            return Err(if cached_status == StatusCode::GONE {
+                AppError::Gone { url: url.to_string() }
+            } else {
+                AppError::UpstreamNotFound { url: url.to_string() }
+            });
+        }
+        
+        METRICS.record_upstream_request();
         
-        // Selector untuk area film terbaru
-        let latest_movies_selector = Selector::parse("#gmr-main-load article.item-infinite").map_err(|e| {
-            error!("Gagal membuat selector: {}", e);
-            AppError::ScrapingError(e.to_string())
+        let request = self.client.get(url).header(header::REFERER, referer.clone());
+        let response = vcr::send(&self.client, request).await.inspect_err(|e| {
+            error!("Gagal melakukan request ke {}: {}", url, e);
         })?;
         
-        let mut movies = Vec::new();
+        let status = response.status;
+        let final_url = response.url.clone();
+        let server = response
+            .headers
+            .get(header::SERVER)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let retry_after = response
+            .headers
+            .get(header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        let mut headers: BTreeMap<String, String> = BTreeMap::new();
+        for (name, value) in response.headers.iter() {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            headers
+                .entry(name.to_string())
+                .and_modify(|joined| {
+                    joined.push_str(", ");
+                    joined.push_str(&value);
+                })
+                .or_insert_with(|| value.into_owned());
+        }
         
-        for movie_element in document.select(&latest_movies_selector) {
-            // Selectors untuk elemen film
-            let title_selector = Selector::parse(".entry-title a").map_err(|e| {
-                AppError::ScrapingError(e.to_string())
-            })?;
-            
-            let poster_selector = Selector::parse(".content-thumbnail img").map_err(|e| {
-                AppError::ScrapingError(e.to_string())
-            })?;
-            
-            let quality_selector = Selector::parse(".gmr-quality-item a").map_err(|e| {
-                AppError::ScrapingError(e.to_string())
-            })?;
-            
-            let rating_selector = Selector::parse(".gmr-rating-item").map_err(|e| {
-                AppError::ScrapingError(e.to_string())
-            })?;
-            
-            let genre_selector = Selector::parse(".gmr-movie-on a").map_err(|e| {
-                AppError::ScrapingError(e.to_string())
-            })?;
-            
-            // Extract data
-            if let Some(title_element) = movie_element.select(&title_selector).next() {
-                let title = title_element.text().next().unwrap_or_default().trim().to_string();
-                let url = title_element.value().attr("href").unwrap_or_default().to_string();
-                let id = url.split('/').filter(|s| !s.is_empty()).last().unwrap_or_default().to_string();
-                
-                let poster = movie_element
-                    .select(&poster_selector)
-                    .next()
-                    .and_then(|el| el.value().attr("src"))
-                    .unwrap_or_default()
-                    .to_string();
-                
-                // Ambil rating (opsional)
-                let rating = movie_element
-                    .select(&rating_selector)
-                    .next()
-                    .and_then(|el| el.text().next())
-                    .and_then(|s| {
-                        // Filter string untuk mendapatkan angka rating
-                        let s = s.trim();
-                        let s = s.trim_start_matches(|c| c == ' ' || c == '\t');
-                        s.parse::<f32>().ok()
-                    });
-                
-                // Ambil kualitas (opsional)
-                let quality = movie_element
-                    .select(&quality_selector)
-                    .next()
-                    .and_then(|el| el.text().next())
-                    .map(|s| s.trim().to_string());
-                
-                // Ekstrak tahun dari judul film
-                let year = if let Some(year_str) = title.split('(').nth(1) {
-                    year_str
-                        .trim_end_matches(')')
-                        .parse::<i32>()
-                        .ok()
-                } else {
-                    None
-                };
-                
-                // Ambil genre
-                let mut genres = Vec::new();
-                for genre_element in movie_element.select(&genre_selector) {
-                    if let Some(genre_text) = genre_element.text().next() {
-                        genres.push(genre_text.trim().to_string());
-                    }
-                }
-                
-                let movie = Movie {
-                    id,
-                    title,
-                    poster,
-                    year,
-                    rating,
-                    quality,
-                    genres,
-                    url,
-                };
-                
-                movies.push(movie);
+        let html = String::from_utf8_lossy(&response.body).into_owned();
+        
+        // Halaman challenge anti-bot bisa datang dengan status 200 maupun 403/503
+        if let Some(marker) = challenge::detect_challenge(status, server.as_deref(), &html) {
+            return Err(Self::upstream_blocked(url, &format!("halaman challenge terdeteksi ({})", marker)));
+        }
+        
+        match status {
+            StatusCode::NOT_FOUND => {
+                info!("Upstream mengembalikan 404 untuk {}", url);
+                self.not_found_cache.insert(url.to_string(), StatusCode::NOT_FOUND).await;
+                return Err(AppError::UpstreamNotFound { url: url.to_string() });
+            },
+            StatusCode::GONE => {
+                info!("Upstream mengembalikan 410 untuk {}", url);
+                self.not_found_cache.insert(url.to_string(), StatusCode::GONE).await;
+                return Err(AppError::Gone { url: url.to_string() });
+            },
+            StatusCode::TOO_MANY_REQUESTS => {
+                warn!("Upstream membatasi request ke {}", url);
+                return Err(AppError::RateLimited {
+                    url: url.to_string(),
+                    retry_after: retry_after.unwrap_or(DEFAULT_RETRY_AFTER),
+                });
+            },
+            _ if !status.is_success() => {
+                error!("Server mengembalikan status error: {}", status);
+                return Err(AppError::UpstreamStatus { url: url.to_string(), status: status.as_u16() });
+            },
+            _ => {}
+        }
+        
+        // WordPress kadang menyajikan halaman "not found" dengan status 200
+        if WP_NOT_FOUND_RE.is_match(&html) {
+            info!("Upstream menyajikan halaman 404 WordPress untuk {}", url);
+            self.not_found_cache.insert(url.to_string(), StatusCode::NOT_FOUND).await;
+            return Err(AppError::UpstreamNotFound { url: url.to_string() });
+        }
+        
+        // Catat URL akhir setelah redirect
+        domain::observe_response(url, &final_url);
+        
+        Ok(UpstreamPage {
+            url: url.to_string(),
+            final_url: final_url.to_string(),
+            status: status.as_u16(),
+            referer,
+            headers,
+            fetched_at: Utc::now(),
+            body: html,
+        })
+    }
+
+    // Parse halaman listing di thread pool blocking agar tidak menahan worker async.
+    // Nomor halaman terakhir dari navigasi dan judul halaman dicatat dengan key cache halaman tanpa
+    // nomor halamannya, halaman pertama tanpa navigasi berarti listing hanya satu halaman.
+    // Halaman tanpa judul dicatat dengan judul kosong agar bisa dibedakan dari judul yang kedaluwarsa.
+    async fn parse_listing(&self, page: UpstreamPage, cache_key: &CacheKey) -> Result<Vec<Movie>, AppError> {
+        let (mut listing, page) = run_blocking(move || (parser::parse_movie_list(&page.body), page)).await?;
+        
+        if let Some((listing_key, page_number)) = cache_key.rsplit_once("_page_") {
+            if let Some(last_page) = listing.last_page.or((page_number == "1").then_some(1)) {
+                self.last_page_cache.insert(format!("{}_last_page", listing_key), last_page).await;
             }
+            let heading = listing.heading.take().unwrap_or_default();
+            self.heading_cache.insert(format!("{}_heading", listing_key), heading).await;
         }
         
-        Ok(movies)
+        if !listing.movies.is_empty() {
+            self.tag_movies(&mut listing.movies);
+            return Ok(listing.movies);
+        }
+        
+        // Simpan HTML halaman yang tidak menghasilkan film agar bisa direproduksi
+        let url = page.url.clone();
+        snapshot::capture_in_background(page, "listing", "daftar film kosong".to_string());
+        
+        // Halaman listing yang valid selalu memiliki container #gmr-main-load,
+        // jika tidak ada berarti upstream menyajikan halaman lain (interstitial, captcha, dll)
+        if !listing.has_container {
+            return Err(Self::upstream_blocked(&url, "container daftar film tidak ditemukan"));
+        }
+        
+        Ok(listing.movies)
+    }
+
+    // Mencatat field yang gagal di-parse ke metrik dan log
+    fn record_parse_warnings(detail: &MovieDetail) {
+        let warnings = detail.parse_warnings.as_deref().unwrap_or_default();
+        let total = METRICS.record_detail_parse(warnings.iter().map(|w| w.field.as_str()));
+        
+        if !warnings.is_empty() {
+            let fields: Vec<&str> = warnings.iter().map(|w| w.field.as_str()).collect();
+            warn!(
+                "Detail film {} tidak lengkap ({:.0}%), field tidak ditemukan: {}",
+                detail.id,
+                detail.completeness * 100.0,
+                fields.join(", ")
+            );
+        }
+        
+        // Ringkasan berkala agar field yang sering gagal mudah terlihat di log
+        if total.is_multiple_of(PARSE_WARNING_LOG_INTERVAL) {
+            info!(
+                "Ringkasan parse detail: {} halaman, peringatan per field: {:?}",
+                total,
+                METRICS.snapshot().parse_warnings
+            );
+        }
+    }
+
+    // Membuat error UpstreamBlocked sekaligus mencatatnya di metrik
+    fn upstream_blocked(url: &str, reason: &str) -> AppError {
+        METRICS.record_upstream_blocked();
+        warn!("Upstream memblokir request ke {}: {}", url, reason);
+        AppError::UpstreamBlocked {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        }
     }
 
     pub async fn get_genres(&self) -> Result<Vec<Genre>, AppError> {
-        info!("Mendapatkan daftar genre dari FilmApik");
+        info!("Mendapatkan daftar genre dari {}", self.site.name);
         
         // Buat cache key untuk genres
-        let cache_key = "all_genres".to_string();
+        let cache_key = self.site_cache_key("all_genres");
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_genres) = self.genres_cache.get(&cache_key).await {
@@ -305,94 +606,8 @@ impl FilmapikScraper {
             return Ok(cached_genres);
         }
         
-        // Daftar genre yang tersedia di FilmApik (secara statis)
-        let genres = vec![
-            Genre {
-                id: "action".to_string(),
-                name: "Action".to_string(),
-                url: format!("{}/genre/action/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "adventure".to_string(),
-                name: "Adventure".to_string(),
-                url: format!("{}/genre/adventure/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "animation".to_string(),
-                name: "Animation".to_string(),
-                url: format!("{}/genre/animation/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "comedy".to_string(),
-                name: "Comedy".to_string(),
-                url: format!("{}/genre/comedy/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "crime".to_string(),
-                name: "Crime".to_string(),
-                url: format!("{}/genre/crime/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "documentary".to_string(),
-                name: "Documentary".to_string(),
-                url: format!("{}/genre/documentary/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "drama".to_string(),
-                name: "Drama".to_string(),
-                url: format!("{}/genre/drama/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "family".to_string(),
-                name: "Family".to_string(),
-                url: format!("{}/genre/family/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "fantasy".to_string(),
-                name: "Fantasy".to_string(),
-                url: format!("{}/genre/fantasy/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "history".to_string(),
-                name: "History".to_string(),
-                url: format!("{}/genre/history/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "horror".to_string(),
-                name: "Horror".to_string(),
-                url: format!("{}/genre/horror/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "music".to_string(),
-                name: "Music".to_string(),
-                url: format!("{}/genre/music/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "mystery".to_string(),
-                name: "Mystery".to_string(),
-                url: format!("{}/genre/mystery/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "romance".to_string(),
-                name: "Romance".to_string(),
-                url: format!("{}/genre/romance/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "science-fiction".to_string(),
-                name: "Science Fiction".to_string(),
-                url: format!("{}/genre/science-fiction/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "thriller".to_string(),
-                name: "Thriller".to_string(),
-                url: format!("{}/genre/thriller/", *FILMAPIK_URL),
-            },
-            Genre {
-                id: "war".to_string(),
-                name: "War".to_string(),
-                url: format!("{}/genre/war/", *FILMAPIK_URL),
-            },
-        ];
+        // Daftar genre dari WordPress jika diaktifkan, jika tidak memakai daftar statis
+        let genres: Vec<Genre> = self.load_terms(Taxonomy::Genre).await.into_iter().map(Genre::from).collect();
         
         // Simpan ke cache untuk penggunaan berikutnya
         self.genres_cache.insert(cache_key, genres.clone()).await;
@@ -404,7 +619,7 @@ impl FilmapikScraper {
         info!("Mengambil daftar film genre {} halaman {}", genre_id, page);
         
         // Buat cache key berdasarkan genre dan halaman
-        let cache_key = format!("genre_{}_page_{}", genre_id, page);
+        let cache_key = self.cache_key(&format!("genre_{}_page_{}", genre_id, page));
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
@@ -413,42 +628,25 @@ impl FilmapikScraper {
         }
         
         // URL untuk halaman genre dengan pagination
+        let base = self.base_url();
         let url = if page > 1 {
-            format!("{}/genre/{}/page/{}/", *FILMAPIK_URL, genre_id, page)
+            format!("{}/genre/{}/page/{}/", base, genre_id, page)
         } else {
-            format!("{}/genre/{}/", *FILMAPIK_URL, genre_id)
+            format!("{}/genre/{}/", base, genre_id)
         };
         
         // Tambahkan referer untuk setiap request
         let referer = if page > 1 {
-            format!("{}/genre/{}/page/{}/", *FILMAPIK_URL, genre_id, page - 1)
+            format!("{}/genre/{}/page/{}/", base, genre_id, page - 1)
         } else {
-            format!("{}/", *FILMAPIK_URL)
+            format!("{}/", base)
         };
         
         // Membuat request dengan referer yang dinamis
-        let response = self.client.get(&url)
-            .header(header::REFERER, referer)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Gagal melakukan request ke {}: {}", url, e);
-                AppError::HttpError(e)
-            })?;
-        
-        let status = response.status();
-        if !status.is_success() {
-            error!("Server mengembalikan status error: {}", status);
-            return Err(AppError::ScrapingError(format!("Status HTTP error: {}", status)));
-        }
-        
-        let html = response.text().await.map_err(|e| {
-            error!("Gagal membaca response HTML: {}", e);
-            AppError::HttpError(e)
-        })?;
+        let upstream_page = self.fetch_page(&url, referer).await?;
         
-        // Gunakan fungsi parse_latest_movies yang sudah ada karena struktur HTML-nya sama
-        let movies = self.parse_latest_movies(&html)?;
+        // Gunakan parser listing yang sama karena struktur HTML-nya sama
+        let movies = self.parse_listing(upstream_page, &cache_key).await?;
         
         // Simpan ke cache untuk penggunaan berikutnya
         self.movies_cache.insert(cache_key, movies.clone()).await;
@@ -457,10 +655,10 @@ impl FilmapikScraper {
     }
 
     pub async fn get_popular_movies(&self, page: u32) -> Result<Vec<Movie>, AppError> {
-        info!("Mengambil daftar film populer (rating tertinggi) dari FilmApik - Halaman {}", page);
+        info!("Mengambil daftar film populer (rating tertinggi) dari {} - Halaman {}", self.site.name, page);
         
         // Buat cache key berdasarkan tipe request dan halaman
-        let cache_key = format!("popular_movies_page_{}", page);
+        let cache_key = self.cache_key(&format!("popular_movies_page_{}", page));
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
@@ -469,42 +667,30 @@ impl FilmapikScraper {
         }
         
         // URL untuk halaman best-rating dengan pagination
+        let base = self.base_url();
         let url = if page > 1 {
-            format!("{}/best-rating/page/{}/", *FILMAPIK_URL, page)
+            format!("{}/best-rating/page/{}/", base, page)
         } else {
-            format!("{}/best-rating/", *FILMAPIK_URL)
+            format!("{}/best-rating/", base)
         };
         
         // Tambahkan referer untuk setiap request yang berbeda
         let referer = if page > 1 {
-            format!("{}/best-rating/page/{}/", *FILMAPIK_URL, page - 1)
+            format!("{}/best-rating/page/{}/", base, page - 1)
         } else {
-            format!("{}/", *FILMAPIK_URL)
+            format!("{}/", base)
         };
         
         // Buat request dengan referer yang dinamis
-        let response = self.client.get(&url)
-            .header(header::REFERER, referer)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Gagal melakukan request ke {}: {}", url, e);
-                AppError::HttpError(e)
-            })?;
-        
-        let status = response.status();
-        if !status.is_success() {
-            error!("Server mengembalikan status error: {}", status);
-            return Err(AppError::ScrapingError(format!("Status HTTP error: {}", status)));
-        }
-        
-        let html = response.text().await.map_err(|e| {
-            error!("Gagal membaca response HTML: {}", e);
-            AppError::HttpError(e)
-        })?;
+        let upstream_page = self.fetch_page(&url, referer).await?;
         
-        // Gunakan fungsi parse_latest_movies yang sudah ada karena struktur HTML-nya sama
-        let movies = self.parse_latest_movies(&html)?;
+        // Gunakan parser listing yang sama karena struktur HTML-nya sama
+        let movies = self.parse_listing(upstream_page, &cache_key).await?;
+        
+        // Daftar film terbaru/populer tidak pernah kosong, hasil kosong berarti upstream menyajikan halaman yang tidak semestinya
+        if movies.is_empty() {
+            return Err(Self::upstream_blocked(&url, "daftar film kosong"));
+        }
         
         // Simpan ke cache untuk penggunaan berikutnya
         self.movies_cache.insert(cache_key, movies.clone()).await;
@@ -516,7 +702,7 @@ impl FilmapikScraper {
         info!("Mengambil detail film dengan ID: {}", movie_id);
         
         // Buat cache key berdasarkan movie_id
-        let cache_key = format!("movie_detail_{}", movie_id);
+        let cache_key = self.cache_key(&format!("movie_detail_{}", movie_id));
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_detail) = self.movie_detail_cache.get(&cache_key).await {
@@ -525,34 +711,38 @@ impl FilmapikScraper {
         }
         
         // Buat URL untuk halaman detail film
-        let url = format!("{}/{}/", *FILMAPIK_URL, movie_id);
+        let base = self.base_url();
+        let url = format!("{}/{}/", base, movie_id);
         
         // Buat referrer
-        let referer = format!("{}/", *FILMAPIK_URL);
+        let referer = format!("{}/", base);
         
         // Lakukan request
-        let response = self.client.get(&url)
-            .header(header::REFERER, referer)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Gagal melakukan request ke {}: {}", url, e);
-                AppError::HttpError(e)
-            })?;
-        
-        let status = response.status();
-        if !status.is_success() {
-            error!("Server mengembalikan status error: {}", status);
-            return Err(AppError::ScrapingError(format!("Status HTTP error: {}", status)));
-        }
-        
-        let html = response.text().await.map_err(|e| {
-            error!("Gagal membaca response HTML: {}", e);
-            AppError::HttpError(e)
-        })?;
+        let upstream_page = self.fetch_page(&url, referer).await?;
         
         // Parse detail film
-        let movie_detail = self.parse_movie_detail(&html, movie_id, &url)?;
+        let detail_id = movie_id.to_string();
+        let (mut movie_detail, upstream_page) = run_blocking(move || {
+            let detail = parser::parse_movie_detail(&upstream_page.body, &detail_id, &upstream_page.url);
+            (detail, upstream_page)
+        }).await?;
+        movie_detail.provider = self.site.id.clone();
+        self.tag_movies(&mut movie_detail.related_movies);
+        
+        Self::record_parse_warnings(&movie_detail);
+        
+        // Simpan HTML jika field utama tidak ditemukan agar bisa direproduksi
+        let missing_core: Vec<&str> = movie_detail
+            .parse_warnings
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|w| w.field.as_str())
+            .filter(|field| DETAIL_CORE_FIELDS.contains(field))
+            .collect();
+        if !missing_core.is_empty() {
+            snapshot::capture_in_background(upstream_page, "detail", format!("field utama tidak ditemukan: {}", missing_core.join(", ")));
+        }
         
         // Simpan ke cache untuk penggunaan berikutnya
         self.movie_detail_cache.insert(cache_key, movie_detail.clone()).await;
@@ -560,418 +750,384 @@ impl FilmapikScraper {
         Ok(movie_detail)
     }
 
-    fn parse_movie_detail(&self, html: &str, movie_id: &str, url: &str) -> Result<MovieDetail, AppError> {
-        let document = Html::parse_document(html);
+    pub async fn get_countries(&self) -> Result<Vec<Country>, AppError> {
+        info!("Mendapatkan daftar negara dari {}", self.site.name);
         
-        // Selector untuk berbagai elemen
-        let title_selector = Selector::parse(".entry-title").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Buat cache key untuk countries
+        let cache_key = self.site_cache_key("all_countries");
         
-        let poster_selector = Selector::parse(".gmr-movie-data img").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Coba ambil dari cache terlebih dahulu
+        if let Some(cached_countries) = self.countries_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk daftar negara");
+            return Ok(cached_countries);
+        }
         
-        let desc_selector = Selector::parse(".gmr-movie-content p").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Daftar negara dari WordPress jika diaktifkan, jika tidak memakai daftar statis
+        let countries: Vec<Country> = self.load_terms(Taxonomy::Country).await.into_iter().map(Country::from).collect();
         
-        // Perbaikan selector untuk rating
-        let rating_selector = Selector::parse("div.gmr-meta-rating > span[itemprop=\"ratingValue\"]").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Simpan ke cache untuk penggunaan berikutnya
+        self.countries_cache.insert(cache_key, countries.clone()).await;
         
-        let meta_info_selector = Selector::parse(".gmr-moviedata").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        Ok(countries)
+    }
+
+    pub async fn get_movies_by_country(&self, country_id: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        info!("Mengambil daftar film negara {} halaman {}", country_id, page);
         
-        let genre_selector = Selector::parse(".gmr-movie-on a").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Buat cache key berdasarkan country dan halaman
+        let cache_key = self.cache_key(&format!("country_{}_page_{}", country_id, page));
         
-        // Selector untuk iframe video
-        let iframe_selector = Selector::parse("iframe").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // Coba ambil dari cache terlebih dahulu
+        if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk film negara {} halaman {}", country_id, page);
+            return Ok(cached_movies);
+        }
         
-        // Selectors untuk film terkait/rekomendasi
-        let related_selector = Selector::parse(".gmr-related-movie .row .item-related").map_err(|e| {
-            AppError::ScrapingError(e.to_string())
-        })?;
+        // URL untuk halaman negara dengan pagination
+        let base = self.base_url();
+        let url = if page > 1 {
+            format!("{}/country/{}/page/{}/", base, country_id, page)
+        } else {
+            format!("{}/country/{}/", base, country_id)
+        };
         
-        // Extract title
-        let title = document
-            .select(&title_selector)
-            .next()
-            .and_then(|el| el.text().next())
-            .map(|s| s.trim().to_string())
-            .unwrap_or_else(|| "Unknown Title".to_string());
-        
-        // Extract poster
-        let poster = document
-            .select(&poster_selector)
-            .next()
-            .and_then(|el| el.value().attr("src"))
-            .map(|s| s.to_string())
-            .unwrap_or_default();
-        
-        // Extract description
-        let description = document
-            .select(&desc_selector)
-            .next()
-            .map(|el| {
-                let desc_text = el.text().collect::<Vec<_>>().join(" ");
-                desc_text.trim().to_string()
-            });
+        // Tambahkan referer untuk setiap request
+        let referer = if page > 1 {
+            format!("{}/country/{}/page/{}/", base, country_id, page - 1)
+        } else {
+            format!("{}/", base)
+        };
         
-        // Extract rating (dengan selector yang sudah diperbaiki)
-        let rating = document
-            .select(&rating_selector)
-            .next()
-            .and_then(|el| el.text().next())
-            .and_then(|s| {
-                s.trim().parse::<f32>().ok()
-            });
+        // Membuat request dengan referer yang dinamis
+        let upstream_page = self.fetch_page(&url, referer).await?;
         
-        // Extract watch URL dari iframe
-        let watch_url = document
-            .select(&iframe_selector)
-            .next()
-            .and_then(|el| el.value().attr("src"))
-            .map(|s| s.to_string());
+        // Gunakan parser listing yang sama karena struktur HTML-nya sama
+        let movies = self.parse_listing(upstream_page, &cache_key).await?;
         
-        // Default values
-        let mut rating_count: Option<u32> = None;
-        let mut quality: Option<String> = None;
-        let mut year: Option<i32> = None;
-        let mut duration: Option<String> = None;
-        let mut country: Option<String> = None;
-        let mut release_date: Option<String> = None;
-        let mut language: Option<String> = None;
-        let mut director: Option<String> = None;
-        let mut views: Option<u32> = None;
-        
-        // Extract metadata information
-        for meta_element in document.select(&meta_info_selector) {
-            let meta_text = meta_element.text().collect::<Vec<_>>().join(" ");
-            let meta_text = meta_text.trim();
-            
-            if meta_text.starts_with("Diposting") && meta_text.contains("dilihat") {
-                // Extract views
-                if let Some(views_str) = meta_text.split("dilihat").nth(1) {
-                    views = views_str
-                        .trim()
-                        .replace(",", "")
-                        .parse::<u32>()
-                        .ok();
-                }
-            } else if meta_text.starts_with("Kualitas:") {
-                quality = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            } else if meta_text.starts_with("Tahun:") {
-                year = meta_text
-                    .split(':')
-                    .nth(1)
-                    .and_then(|s| s.trim().parse::<i32>().ok());
-            } else if meta_text.starts_with("Durasi:") {
-                duration = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            } else if meta_text.starts_with("Negara:") {
-                country = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            } else if meta_text.starts_with("Rilis:") {
-                release_date = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            } else if meta_text.starts_with("Bahasa:") {
-                language = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            } else if meta_text.starts_with("Direksi:") {
-                director = meta_text.split(':').nth(1).map(|s| s.trim().to_string());
-            }
-        }
+        // Simpan ke cache untuk penggunaan berikutnya
+        self.movies_cache.insert(cache_key, movies.clone()).await;
         
-        // Extract genres
-        let mut genres = Vec::new();
-        for genre_element in document.select(&genre_selector) {
-            if let Some(genre_text) = genre_element.text().next() {
-                let genre_text = genre_text.trim();
-                if !genre_text.is_empty() {
-                    genres.push(genre_text.to_string());
-                }
-            }
-        }
+        Ok(movies)
+    }
+    
+    pub async fn get_movies_by_year(&self, year: i32, page: u32) -> Result<Vec<Movie>, AppError> {
+        info!("Mengambil daftar film tahun {} halaman {}", year, page);
+        self.get_archive_movies(Taxonomy::Year, &year.to_string(), page).await
+    }
+    
+    pub async fn get_movies_by_quality(&self, quality: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        info!("Mengambil daftar film kualitas {} halaman {}", quality, page);
+        // Slug kualitas di URL selalu huruf kecil ("BluRay" -> "bluray")
+        self.get_archive_movies(Taxonomy::Quality, &quality.to_ascii_lowercase(), page).await
+    }
+    
+    // Film seseorang dari halaman pemeran (/cast/{id}/) dan sutradara (/director/{id}/) yang diambil bersamaan.
+    // Halaman yang tidak ada (404) berarti orang tersebut tidak memiliki peran itu.
+    pub async fn get_person_movies(&self, person_id: &str, page: u32) -> Result<PersonMovies, AppError> {
+        info!("Mengambil daftar film orang {} halaman {}", person_id, page);
+        
+        let (as_cast, as_director) = futures::join!(
+            self.get_archive_movies(Taxonomy::Cast, person_id, page),
+            self.get_archive_movies(Taxonomy::Director, person_id, page),
+        );
         
-        // Extract actors
-        let mut actors = Vec::new();
-        let actors_selector = Selector::parse(".gmr-castcrew li").ok();
-        if let Some(selector) = actors_selector {
-            for actor_element in document.select(&selector) {
-                if let Some(actor_text) = actor_element.text().next() {
-                    let actor_text = actor_text.trim();
-                    if !actor_text.is_empty() {
-                        actors.push(actor_text.to_string());
+        let base = self.base_url();
+        let mut roles = Vec::new();
+        let mut primary = None;
+        let mut movies: Vec<Movie> = Vec::new();
+        for (taxonomy, result) in [(Taxonomy::Cast, as_cast), (Taxonomy::Director, as_director)] {
+            match result {
+                Ok(role_movies) => {
+                    roles.push(taxonomy.path().to_string());
+                    primary.get_or_insert(taxonomy);
+                    for movie in role_movies {
+                        if !movies.iter().any(|m| m.id == movie.id) {
+                            movies.push(movie);
+                        }
                     }
-                }
+                },
+                Err(AppError::UpstreamNotFound { .. }) => {},
+                Err(e) => return Err(e),
             }
         }
         
-        // Extract related movies
-        let mut related_movies = Vec::new();
-        for related_element in document.select(&related_selector) {
-            let title_selector = Selector::parse("h3 a").ok();
-            let poster_selector = Selector::parse("img").ok();
-            
-            if let (Some(title_sel), Some(poster_sel)) = (title_selector, poster_selector) {
-                let related_title = related_element
-                    .select(&title_sel)
-                    .next()
-                    .and_then(|el| el.text().next())
-                    .map(|s| s.trim().to_string());
-                
-                let related_url = related_element
-                    .select(&title_sel)
-                    .next()
-                    .and_then(|el| el.value().attr("href"))
-                    .map(|s| s.to_string());
-                
-                let related_poster = related_element
-                    .select(&poster_sel)
-                    .next()
-                    .and_then(|el| el.value().attr("src"))
-                    .map(|s| s.to_string());
-                
-                if let (Some(title), Some(url)) = (related_title, related_url) {
-                    let id = url.split('/').filter(|s| !s.is_empty()).last().unwrap_or_default().to_string();
-                    let movie = Movie {
-                        id,
-                        title,
-                        poster: related_poster.unwrap_or_default(),
-                        year: None,
-                        rating: None,
-                        quality: None,
-                        genres: Vec::new(),
-                        url,
-                    };
-                    related_movies.push(movie);
-                }
-            }
-        }
+        let Some(primary) = primary else {
+            return Err(AppError::NotFoundError(format!("Pemeran atau sutradara {} tidak ditemukan", person_id)));
+        };
         
-        let movie_detail = MovieDetail {
-            id: movie_id.to_string(),
-            title,
-            poster,
-            rating,
-            rating_count,
-            description,
-            views,
-            genres,
-            quality,
-            year,
-            duration,
-            country,
-            release_date,
-            language,
-            director,
-            actors,
-            url: url.to_string(),
-            watch_url,
-            related_movies,
+        // Nama diambil dari judul halaman arsip yang dicatat parse_listing (tanpa label "Cast:" dsb).
+        // Slug hanya dipakai jika halaman tidak memiliki judul, karena slug kehilangan tanda hubung dan
+        // huruf besar asli nama.
+        let name = match self.archive_heading(primary, person_id, page).await {
+            Ok(Some(heading)) if !heading.is_empty() => heading,
+            Ok(_) => wordpress::name_from_slug(person_id),
+            Err(e) => {
+                warn!("Judul halaman {} tidak bisa diambil ulang: {}", person_id, e);
+                wordpress::name_from_slug(person_id)
+            },
         };
         
-        Ok(movie_detail)
+        let person = Person {
+            id: person_id.to_string(),
+            name,
+            url: format!("{}/{}/{}/", base, primary.path(), person_id),
+        };
+        
+        Ok(PersonMovies { person, roles, movies })
     }
-
-    pub async fn get_countries(&self) -> Result<Vec<Country>, AppError> {
-        info!("Mendapatkan daftar negara dari FilmApik");
+    
+    // Judul halaman arsip yang dicatat saat halaman di-parse. Jika halaman masih ada di cache film tetapi
+    // judulnya sudah tidak ada di cache, halaman diambil ulang dari upstream seperti last_page.
+    async fn archive_heading(&self, taxonomy: Taxonomy, slug: &str, page: u32) -> Result<Option<String>, AppError> {
+        let path = taxonomy.path();
+        let heading_key = self.cache_key(&format!("{}_{}_heading", path, slug));
+        if let Some(heading) = self.heading_cache.get(&heading_key).await {
+            return Ok(Some(heading));
+        }
         
-        // Buat cache key untuk countries
-        let cache_key = "all_countries".to_string();
+        self.movies_cache.invalidate(&self.cache_key(&format!("{}_{}_page_{}", path, slug, page))).await;
+        self.get_archive_movies(taxonomy, slug, page).await?;
+        
+        Ok(self.heading_cache.get(&heading_key).await)
+    }
+    
+    // Halaman arsip taksonomi (/{path}/{slug}/page/{n}/) memakai struktur listing yang sama
+    async fn get_archive_movies(&self, taxonomy: Taxonomy, slug: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        let path = taxonomy.path();
+        
+        // Buat cache key berdasarkan taksonomi, slug dan halaman
+        let cache_key = self.cache_key(&format!("{}_{}_page_{}", path, slug, page));
         
         // Coba ambil dari cache terlebih dahulu
-        if let Some(cached_countries) = self.countries_cache.get(&cache_key).await {
-            info!("Menggunakan cache untuk daftar negara");
-            return Ok(cached_countries);
+        if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk film {} {} halaman {}", path, slug, page);
+            return Ok(cached_movies);
         }
         
-        // Daftar negara yang tersedia di FilmApik (secara statis)
-        // Data diambil dari film-terbaru.html dan ditambahkan negara lainnya yang populer
-        let countries = vec![
-            Country {
-                id: "usa".to_string(),
-                name: "USA".to_string(),
-                url: format!("{}/country/usa/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "india".to_string(),
-                name: "India".to_string(),
-                url: format!("{}/country/india/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "china".to_string(),
-                name: "China".to_string(),
-                url: format!("{}/country/china/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "korea".to_string(),
-                name: "Korea".to_string(),
-                url: format!("{}/country/korea/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "philippines".to_string(),
-                name: "Philippines".to_string(),
-                url: format!("{}/country/philippines/", *FILMAPIK_URL),
-            },
-            // Tambahan negara-negara lainnya
-            Country {
-                id: "japan".to_string(),
-                name: "Japan".to_string(),
-                url: format!("{}/country/japan/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "thailand".to_string(),
-                name: "Thailand".to_string(),
-                url: format!("{}/country/thailand/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "indonesia".to_string(),
-                name: "Indonesia".to_string(),
-                url: format!("{}/country/indonesia/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "malaysia".to_string(),
-                name: "Malaysia".to_string(),
-                url: format!("{}/country/malaysia/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "france".to_string(),
-                name: "France".to_string(),
-                url: format!("{}/country/france/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "germany".to_string(),
-                name: "Germany".to_string(),
-                url: format!("{}/country/germany/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "uk".to_string(),
-                name: "United Kingdom".to_string(),
-                url: format!("{}/country/uk/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "italy".to_string(),
-                name: "Italy".to_string(),
-                url: format!("{}/country/italy/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "spain".to_string(),
-                name: "Spain".to_string(),
-                url: format!("{}/country/spain/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "russia".to_string(),
-                name: "Russia".to_string(),
-                url: format!("{}/country/russia/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "australia".to_string(),
-                name: "Australia".to_string(),
-                url: format!("{}/country/australia/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "canada".to_string(),
-                name: "Canada".to_string(),
-                url: format!("{}/country/canada/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "brazil".to_string(),
-                name: "Brazil".to_string(),
-                url: format!("{}/country/brazil/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "mexico".to_string(),
-                name: "Mexico".to_string(),
-                url: format!("{}/country/mexico/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "taiwan".to_string(),
-                name: "Taiwan".to_string(),
-                url: format!("{}/country/taiwan/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "hongkong".to_string(),
-                name: "Hong Kong".to_string(),
-                url: format!("{}/country/hongkong/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "vietnam".to_string(),
-                name: "Vietnam".to_string(),
-                url: format!("{}/country/vietnam/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "turkey".to_string(),
-                name: "Turkey".to_string(),
-                url: format!("{}/country/turkey/", *FILMAPIK_URL),
-            },
-            Country {
-                id: "singapore".to_string(),
-                name: "Singapore".to_string(),
-                url: format!("{}/country/singapore/", *FILMAPIK_URL),
-            },
-        ];
+        let base = self.base_url();
+        let url = if page > 1 {
+            format!("{}/{}/{}/page/{}/", base, path, slug, page)
+        } else {
+            format!("{}/{}/{}/", base, path, slug)
+        };
+        
+        let referer = if page > 1 {
+            format!("{}/{}/{}/page/{}/", base, path, slug, page - 1)
+        } else {
+            format!("{}/", base)
+        };
+        
+        let upstream_page = self.fetch_page(&url, referer).await?;
+        let movies = self.parse_listing(upstream_page, &cache_key).await?;
         
         // Simpan ke cache untuk penggunaan berikutnya
-        self.countries_cache.insert(cache_key, countries.clone()).await;
+        self.movies_cache.insert(cache_key, movies.clone()).await;
         
-        Ok(countries)
+        Ok(movies)
     }
-
-    pub async fn get_movies_by_country(&self, country_id: &str, page: u32) -> Result<Vec<Movie>, AppError> {
-        info!("Mengambil daftar film negara {} halaman {}", country_id, page);
+    
+    pub async fn search_movies(&self, query: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        info!("Mencari film \"{}\" di {} halaman {}", query, self.site.name, page);
         
-        // Buat cache key berdasarkan country dan halaman
-        let cache_key = format!("country_{}_page_{}", country_id, page);
+        // Buat cache key berdasarkan kata kunci dan halaman
+        let cache_key = self.cache_key(&format!("search_{}_page_{}", query.to_lowercase(), page));
         
         // Coba ambil dari cache terlebih dahulu
         if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
-            info!("Menggunakan cache untuk film negara {} halaman {}", country_id, page);
+            info!("Menggunakan cache untuk pencarian \"{}\" halaman {}", query, page);
             return Ok(cached_movies);
         }
         
-        // URL untuk halaman negara dengan pagination
+        // URL pencarian WordPress dengan pagination
+        let base = self.base_url();
+        let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
         let url = if page > 1 {
-            format!("{}/country/{}/page/{}/", *FILMAPIK_URL, country_id, page)
+            format!("{}/page/{}/?s={}&post_type[]=post", base, page, encoded)
         } else {
-            format!("{}/country/{}/", *FILMAPIK_URL, country_id)
+            format!("{}/?s={}&post_type[]=post", base, encoded)
         };
         
-        // Tambahkan referer untuk setiap request
-        let referer = if page > 1 {
-            format!("{}/country/{}/page/{}/", *FILMAPIK_URL, country_id, page - 1)
-        } else {
-            format!("{}/", *FILMAPIK_URL)
+        let referer = format!("{}/", base);
+        let upstream_page = self.fetch_page(&url, referer).await?;
+        
+        // Hasil pencarian boleh kosong, jadi tidak memakai pemeriksaan container di parse_listing
+        let mut movies = run_blocking(move || parser::parse_movie_list(&upstream_page.body).movies).await?;
+        self.tag_movies(&mut movies);
+        
+        // Simpan ke cache untuk penggunaan berikutnya
+        self.movies_cache.insert(cache_key, movies.clone()).await;
+        
+        Ok(movies)
+    }
+    
+    // Term taksonomi dari REST API/sitemap WordPress jika CATALOG_SOURCE=wordpress,
+    // jika tidak tersedia memakai daftar statis
+    async fn load_terms(&self, taxonomy: Taxonomy) -> Vec<TaxonomyTerm> {
+        if wordpress::current() == CatalogSource::Wordpress {
+            match wordpress::fetch_terms(self, taxonomy).await {
+                Ok(terms) if !terms.is_empty() => return terms,
+                Ok(_) => warn!("Daftar {} dari WordPress {} kosong, memakai daftar statis", taxonomy.wp_name(), self.site.name),
+                Err(e) => warn!("Gagal mengambil daftar {} dari WordPress {}: {}, memakai daftar statis", taxonomy.wp_name(), self.site.name, e),
+            }
+        }
+        
+        self.static_terms(taxonomy)
+    }
+    
+    // Daftar statis dipakai jika WordPress tidak diaktifkan atau tidak tersedia.
+    // Tahun dibentuk dari rentang MIN_YEAR sampai tahun ini, pemeran dan sutradara tidak memiliki daftar statis.
+    fn static_terms(&self, taxonomy: Taxonomy) -> Vec<TaxonomyTerm> {
+        let entries: Vec<(String, String)> = match taxonomy {
+            Taxonomy::Genre => Self::table_entries(&GENRES),
+            Taxonomy::Country => Self::table_entries(&COUNTRIES),
+            Taxonomy::Quality => Self::table_entries(&QUALITIES),
+            Taxonomy::Year => (MIN_YEAR..=Utc::now().year())
+                .rev()
+                .map(|year| (year.to_string(), year.to_string()))
+                .collect(),
+            Taxonomy::Cast | Taxonomy::Director => Vec::new(),
         };
         
-        // Membuat request dengan referer yang dinamis
-        let response = self.client.get(&url)
-            .header(header::REFERER, referer)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Gagal melakukan request ke {}: {}", url, e);
-                AppError::HttpError(e)
-            })?;
-        
-        let status = response.status();
-        if !status.is_success() {
-            error!("Server mengembalikan status error: {}", status);
-            return Err(AppError::ScrapingError(format!("Status HTTP error: {}", status)));
-        }
-        
-        let html = response.text().await.map_err(|e| {
-            error!("Gagal membaca response HTML: {}", e);
-            AppError::HttpError(e)
-        })?;
+        let base = self.base_url();
+        entries
+            .into_iter()
+            .map(|(id, name)| TaxonomyTerm {
+                url: format!("{}/{}/{}/", base, taxonomy.path(), id),
+                id,
+                name,
+                count: None,
+            })
+            .collect()
+    }
+    
+    fn table_entries(table: &[(&str, &str)]) -> Vec<(String, String)> {
+        table.iter().map(|(id, name)| (id.to_string(), name.to_string())).collect()
+    }
+    
+    pub async fn get_terms(&self, taxonomy: Taxonomy) -> Result<Vec<TaxonomyTerm>, AppError> {
+        info!("Mendapatkan daftar {} dari {}", taxonomy.wp_name(), self.site.name);
         
-        // Gunakan fungsi parse_latest_movies yang sudah ada karena struktur HTML-nya sama
-        let movies = self.parse_latest_movies(&html)?;
+        let cache_key = self.site_cache_key(&format!("terms_{}", taxonomy.wp_name()));
+        if let Some(cached_terms) = self.terms_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk daftar {}", taxonomy.wp_name());
+            return Ok(cached_terms);
+        }
+        
+        let terms = self.load_terms(taxonomy).await;
+        self.terms_cache.insert(cache_key, terms.clone()).await;
+        
+        Ok(terms)
+    }
+    
+    // Katalog seluruh film situs per halaman. Dengan CATALOG_SOURCE=wordpress diambil dari
+    // REST API (lalu sitemap) sehingga total film dan halaman diketahui, jika tidak dari halaman terbaru.
+    pub async fn get_catalog(&self, page: u32) -> Result<CatalogPage, AppError> {
+        info!("Mengambil katalog film {} halaman {}", self.site.name, page);
+        
+        let cache_key = self.cache_key(&format!("catalog_page_{}", page));
+        if let Some(cached_catalog) = self.catalog_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk katalog halaman {}", page);
+            return Ok(cached_catalog);
+        }
+        
+        let catalog = match wordpress::current() {
+            CatalogSource::Wordpress => match self.wordpress_catalog(page).await {
+                Ok(catalog) => catalog,
+                Err(e) => {
+                    warn!("Katalog WordPress {} tidak tersedia: {}, memakai halaman HTML", self.site.name, e);
+                    self.html_catalog(page).await?
+                },
+            },
+            CatalogSource::Html => self.html_catalog(page).await?,
+        };
+        
+        self.catalog_cache.insert(cache_key, catalog.clone()).await;
+        
+        Ok(catalog)
+    }
+    
+    async fn wordpress_catalog(&self, page: u32) -> Result<CatalogPage, AppError> {
+        let rest_error = match wordpress::fetch_rest_catalog(self, page).await {
+            Ok(catalog) => return Ok(catalog),
+            Err(e) => e,
+        };
+        warn!("REST API posts {} tidak tersedia: {}, mencoba sitemap", self.site.name, rest_error);
+        
+        let cache_key = self.cache_key("catalog_sitemap");
+        let entries = match self.sitemap_cache.get(&cache_key).await {
+            Some(entries) => entries,
+            None => {
+                let entries = Arc::new(wordpress::fetch_sitemap_catalog(self).await?);
+                if entries.is_empty() {
+                    return Err(rest_error);
+                }
+                self.sitemap_cache.insert(cache_key, entries.clone()).await;
+                entries
+            },
+        };
+        
+        let per_page = wordpress::REST_PER_PAGE;
+        let total = entries.len() as u32;
+        Ok(CatalogPage {
+            page,
+            per_page,
+            total_items: Some(total),
+            total_pages: Some(total.div_ceil(per_page)),
+            source: "sitemap".to_string(),
+            entries: entries
+                .iter()
+                .skip((page.saturating_sub(1) * per_page) as usize)
+                .take(per_page as usize)
+                .cloned()
+                .collect(),
+        })
+    }
+    
+    // Tanpa WordPress, katalog adalah halaman film terbaru dan totalnya tidak diketahui
+    async fn html_catalog(&self, page: u32) -> Result<CatalogPage, AppError> {
+        let movies = self.get_latest_movies(page).await?;
+        
+        Ok(CatalogPage {
+            page,
+            per_page: movies.len() as u32,
+            total_items: None,
+            total_pages: None,
+            source: "html".to_string(),
+            entries: movies
+                .into_iter()
+                .map(|movie| CatalogEntry {
+                    id: movie.id,
+                    title: Some(movie.title),
+                    url: movie.url,
+                    modified: None,
+                    provider: movie.provider,
+                })
+                .collect(),
+        })
+    }
+    
+    // Pencarian lanjutan tema Muvipro (/?s=&search=advanced&genre=...&movieyear=...), hasilnya boleh kosong.
+    // Situs yang tidak mendukungnya mengembalikan hasil pencarian biasa yang tidak difilter.
+    pub async fn get_advanced_search(&self, query: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        info!("Pencarian lanjutan di {} halaman {}: {}", self.site.name, page, query);
+        
+        let cache_key = self.cache_key(&format!("advanced_{}_page_{}", query, page));
+        if let Some(cached_movies) = self.movies_cache.get(&cache_key).await {
+            info!("Menggunakan cache untuk pencarian lanjutan halaman {}", page);
+            return Ok(cached_movies);
+        }
+        
+        let base = self.base_url();
+        let url = if page > 1 {
+            format!("{}/page/{}/?{}", base, page, query)
+        } else {
+            format!("{}/?{}", base, query)
+        };
+        
+        let upstream_page = self.fetch_page(&url, format!("{}/", base)).await?;
+        let mut movies = run_blocking(move || parser::parse_movie_list(&upstream_page.body).movies).await?;
+        self.tag_movies(&mut movies);
         
-        // Simpan ke cache untuk penggunaan berikutnya
         self.movies_cache.insert(cache_key, movies.clone()).await;
         
         Ok(movies)
@@ -984,6 +1140,12 @@ impl FilmapikScraper {
         self.genres_cache.invalidate_all();
         self.countries_cache.invalidate_all();
         self.movie_detail_cache.invalidate_all();
+        self.terms_cache.invalidate_all();
+        self.catalog_cache.invalidate_all();
+        self.sitemap_cache.invalidate_all();
+        self.not_found_cache.invalidate_all();
+        self.last_page_cache.invalidate_all();
+        self.heading_cache.invalidate_all();
     }
     
     // Method untuk memperbarui cache tertentu secara manual
@@ -992,15 +1154,15 @@ impl FilmapikScraper {
             "genres" => {
                 info!("Memperbarui cache untuk genre");
                 let genres = self.get_genres().await?;
-                self.genres_cache.insert("all_genres".to_string(), genres).await;
+                self.genres_cache.insert(self.site_cache_key("all_genres"), genres).await;
             },
             "countries" => {
                 info!("Memperbarui cache untuk negara");
                 let countries = self.get_countries().await?;
-                self.countries_cache.insert("all_countries".to_string(), countries).await;
+                self.countries_cache.insert(self.site_cache_key("all_countries"), countries).await;
             },
             _ => {
-                return Err(AppError::ScrapingError(format!("Tipe cache tidak dikenali: {}", cache_type)));
+                return Err(AppError::invalid_parameter("cache_type", format!("Tipe cache tidak dikenali: {}", cache_type)));
             }
         }
         
@@ -1013,10 +1175,67 @@ impl FilmapikScraper {
         let genres_stats = self.genres_cache.entry_count();
         let countries_stats = self.countries_cache.entry_count();
         let details_stats = self.movie_detail_cache.entry_count();
+        let not_found_stats = self.not_found_cache.entry_count();
         
         format!(
-            "Cache Stats: Movies: {}, Genres: {}, Countries: {}, Details: {}",
-            movies_stats, genres_stats, countries_stats, details_stats
+            "Cache Stats: Movies: {}, Genres: {}, Countries: {}, Details: {}, NotFound: {}",
+            movies_stats, genres_stats, countries_stats, details_stats, not_found_stats
         )
     }
-} 
\ No newline at end of file
+}
+
+// Menjalankan pekerjaan CPU-bound (parsing HTML) di thread pool blocking milik tokio
+pub(crate) async fn run_blocking<T, F>(f: F) -> Result<T, AppError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Gagal menjalankan parser: {}", e)))
+}
+
+// Parse header Retry-After yang bisa berupa jumlah detik atau tanggal HTTP
+fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+    
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| (date.with_timezone(&Utc) - Utc::now()).num_seconds().max(0) as u64)
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const LISTING_HTML: &str = include_str!("../../tests/fixtures/listing.html");
+
+    #[tokio::test]
+    async fn person_name_survives_heading_eviction() {
+        let server = MockServer::start().await;
+        let heading = LISTING_HTML.replace(">Film Terbaru</h1>", ">Cast: Lee Jung-jae</h1>");
+        Mock::given(method("GET"))
+            .and(path("/cast/lee-jung-jae/"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(heading, "text/html; charset=UTF-8"))
+            .mount(&server)
+            .await;
+        let scraper = FilmapikScraper::new().with_site(Site {
+            id: "mirror".to_string(),
+            name: "Mirror".to_string(),
+            base_url: Some(server.uri()),
+        });
+
+        let person = scraper.get_person_movies("lee-jung-jae", 1).await.unwrap();
+        assert_eq!(person.person.name, "Lee Jung-jae");
+
+        // Listing masih ada di cache film tetapi judulnya sudah tidak ada di cache
+        scraper.heading_cache.invalidate_all();
+        let person = scraper.get_person_movies("lee-jung-jae", 1).await.unwrap();
+        assert_eq!(person.person.name, "Lee Jung-jae");
+    }
+}
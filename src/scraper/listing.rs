@@ -0,0 +1,459 @@
+use crate::errors::AppError;
+use crate::models::movie::Movie;
+use crate::scraper::filmapik::FilmapikScraper;
+use futures::future::join_all;
+use log::{info, warn};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+// Jumlah film per halaman upstream, biasanya 20 atau 24 film di FilmApik
+pub const UPSTREAM_PER_PAGE: u32 = 24;
+
+// Listing upstream yang bisa diurutkan ulang dan dipotong ulang di sisi API
+#[derive(Debug, Clone, PartialEq)]
+pub enum Listing {
+    Latest,
+    Popular,
+    Genre(String),
+    Country(String),
+    Year(i32),
+    Quality(String),
+    Search(String),
+}
+
+impl Listing {
+    // Identitas listing yang disimpan di cursor agar cursor tidak bisa dipakai di listing lain
+    pub fn key(&self) -> String {
+        match self {
+            Listing::Latest => "latest".to_string(),
+            Listing::Popular => "popular".to_string(),
+            Listing::Genre(genre) => format!("genre:{}", genre),
+            Listing::Country(country) => format!("country:{}", country),
+            Listing::Year(year) => format!("year:{}", year),
+            Listing::Quality(quality) => format!("quality:{}", quality),
+            Listing::Search(query) => format!("search:{}", query),
+        }
+    }
+
+    pub(crate) async fn load(&self, scraper: &FilmapikScraper, page: u32) -> Result<Vec<Movie>, AppError> {
+        match self {
+            Listing::Latest => scraper.get_latest_movies(page).await,
+            Listing::Popular => scraper.get_popular_movies(page).await,
+            Listing::Genre(genre) => scraper.get_movies_by_genre(genre, page).await,
+            Listing::Country(country) => scraper.get_movies_by_country(country, page).await,
+            Listing::Year(year) => scraper.get_movies_by_year(*year, page).await,
+            Listing::Quality(quality) => scraper.get_movies_by_quality(quality, page).await,
+            Listing::Search(query) => scraper.search_movies(query, page).await,
+        }
+    }
+}
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Listing::Latest => write!(f, "terbaru"),
+            Listing::Popular => write!(f, "populer"),
+            Listing::Genre(genre) => write!(f, "genre {}", genre),
+            Listing::Country(country) => write!(f, "negara {}", country),
+            Listing::Year(year) => write!(f, "tahun {}", year),
+            Listing::Quality(quality) => write!(f, "kualitas {}", quality),
+            Listing::Search(query) => write!(f, "pencarian \"{}\"", query),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Rating,
+    Year,
+    Title,
+    // Jumlah penonton hanya diketahui untuk film yang detailnya sudah ada di cache
+    Views,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListingSort {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+impl ListingSort {
+    pub fn parse_field(value: &str) -> Result<SortField, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "rating" => Ok(SortField::Rating),
+            "year" => Ok(SortField::Year),
+            "title" => Ok(SortField::Title),
+            "views" => Ok(SortField::Views),
+            other => Err(format!("Urutan tidak dikenal: {} (pilihan: rating, year, title, views)", other)),
+        }
+    }
+
+    pub fn parse_order(value: &str) -> Result<SortOrder, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            other => Err(format!("Arah urutan tidak dikenal: {} (pilihan: asc, desc)", other)),
+        }
+    }
+
+    // Arah default: judul A-Z, selain itu nilai terbesar dulu
+    pub fn new(field: SortField, order: Option<SortOrder>) -> Self {
+        let order = order.unwrap_or(match field {
+            SortField::Title => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        });
+        ListingSort { field, order }
+    }
+
+    // Sort stabil: film dengan nilai sama tetap mengikuti urutan upstream,
+    // film tanpa nilai (rating/tahun/views tidak diketahui) selalu di akhir apa pun arahnya
+    pub fn sort(self, movies: &mut [Movie]) {
+        let key = |m: &Movie| -> Option<SortKey> {
+            match self.field {
+                SortField::Rating => m.rating.map(SortKey::Float),
+                SortField::Year => m.year.map(|y| SortKey::Int(y as i64)),
+                SortField::Title => Some(SortKey::Text(m.title.to_lowercase())),
+                SortField::Views => m.views.map(|v| SortKey::Int(v as i64)),
+            }
+        };
+
+        movies.sort_by_cached_key(|m| {
+            let key = key(m);
+            (key.is_none(), key.map(|k| Directed(k, self.order)))
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SortKey {
+    Int(i64),
+    Float(f32),
+    Text(String),
+}
+
+impl SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Int(a), SortKey::Int(b)) => a.cmp(b),
+            (SortKey::Float(a), SortKey::Float(b)) => a.total_cmp(b),
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            // Satu field selalu menghasilkan jenis kunci yang sama
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+// Kunci urutan beserta arahnya agar bisa dipakai langsung oleh sort_by_cached_key
+#[derive(Debug, Clone, PartialEq)]
+struct Directed(SortKey, SortOrder);
+
+impl Eq for Directed {}
+
+impl PartialOrd for Directed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Directed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.1 {
+            SortOrder::Asc => self.0.cmp(&other.0),
+            SortOrder::Desc => other.0.cmp(&self.0),
+        }
+    }
+}
+
+// Posisi film di listing upstream, dipakai sebagai penanda cursor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Anchor {
+    pub id: String,
+    pub page: u32,
+}
+
+// Arah pengambilan dari penanda: film setelah atau sebelum penanda
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    After,
+    Before,
+}
+
+// Film dari beberapa halaman upstream yang digabung sesuai urutan upstream tanpa duplikat
+struct Stitched {
+    movies: Vec<Movie>,
+    // Halaman upstream tempat setiap film ditemukan
+    pages: Vec<u32>,
+    seen: HashSet<String>,
+    next_page: u32,
+    // Halaman 404 atau kosong ditemukan, tidak ada halaman berikutnya
+    exhausted: bool,
+    // Pengambilan berhenti karena halaman upstream gagal
+    truncated: bool,
+}
+
+impl Stitched {
+    fn starting_at(page: u32) -> Self {
+        Stitched {
+            movies: Vec::new(),
+            pages: Vec::new(),
+            seen: HashSet::new(),
+            next_page: page.max(1),
+            exhausted: false,
+            truncated: false,
+        }
+    }
+
+    fn can_continue(&self) -> bool {
+        !self.exhausted && !self.truncated
+    }
+
+    fn position(&self, id: &str) -> Option<usize> {
+        self.movies.iter().position(|m| m.id == id)
+    }
+
+    // Mengambil `count` halaman berikutnya secara bersamaan. Kegagalan halaman pertama dikembalikan
+    // sebagai error, kegagalan halaman berikutnya hanya memotong hasil.
+    async fn fetch(&mut self, scraper: &FilmapikScraper, listing: &Listing, count: u32) -> Result<(), AppError> {
+        let first = self.next_page;
+        let results = join_all((first..first + count.max(1)).map(|page| listing.load(scraper, page))).await;
+
+        for (page, result) in (first..).zip(results) {
+            self.next_page = page + 1;
+            match result {
+                Ok(movies) if !movies.is_empty() => {
+                    for movie in movies {
+                        if self.seen.insert(movie.id.clone()) {
+                            self.movies.push(movie);
+                            self.pages.push(page);
+                        }
+                    }
+                },
+                Ok(_) => self.exhausted = true,
+                Err(e) if page == first && self.movies.is_empty() => return Err(e),
+                Err(AppError::UpstreamNotFound { .. }) => self.exhausted = true,
+                Err(e) => {
+                    warn!("Listing {} dipotong di halaman {}: {}", listing, page, e);
+                    self.truncated = true;
+                },
+            }
+            if !self.can_continue() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // Mengambil halaman tambahan sampai ada `wanted` film. Dibatasi dua putaran karena halaman
+    // yang seluruh isinya duplikat tidak menambah film.
+    async fn fill(&mut self, scraper: &FilmapikScraper, listing: &Listing, wanted: usize) -> Result<(), AppError> {
+        for _ in 0..2 {
+            if self.movies.len() >= wanted || !self.can_continue() {
+                break;
+            }
+            let missing = (wanted - self.movies.len()) as u32;
+            self.fetch(scraper, listing, missing.div_ceil(UPSTREAM_PER_PAGE)).await?;
+        }
+        Ok(())
+    }
+
+    fn anchor(&self, index: usize) -> Anchor {
+        Anchor {
+            id: self.movies[index].id.clone(),
+            page: self.pages[index],
+        }
+    }
+
+    fn slice(mut self, start: usize, limit: usize, has_previous: bool, total_items: Option<u32>) -> ListingRange {
+        let start = start.min(self.movies.len());
+        let end = (start + limit).min(self.movies.len());
+        let (first, last) = if start < end {
+            (Some(self.anchor(start)), Some(self.anchor(end - 1)))
+        } else {
+            (None, None)
+        };
+        let has_more = end < self.movies.len() || !self.exhausted;
+
+        ListingRange {
+            movies: self.movies.drain(start..end).collect(),
+            total_items,
+            first,
+            last,
+            has_previous,
+            has_more,
+        }
+    }
+}
+
+// Mengambil halaman 1..=pages dari listing secara bersamaan lalu menggabungkannya sesuai urutan upstream.
+// Halaman 404 atau kosong berarti listing sudah habis, kegagalan setelah halaman pertama hanya memotong jendela.
+pub async fn load_window(scraper: &FilmapikScraper, listing: &Listing, pages: u32) -> Result<Vec<Movie>, AppError> {
+    info!("Mengambil {} halaman listing {} untuk diurutkan", pages, listing);
+
+    let mut stitched = Stitched::starting_at(1);
+    stitched.fetch(scraper, listing, pages).await?;
+
+    let mut movies = stitched.movies;
+    scraper.fill_known_views(&mut movies).await;
+    Ok(movies)
+}
+
+// Potongan listing yang tidak terikat ukuran halaman upstream
+#[derive(Debug, Clone)]
+pub struct ListingRange {
+    pub movies: Vec<Movie>,
+    // Hanya diketahui jika halaman terakhir listing ikut terambil
+    pub total_items: Option<u32>,
+    // Posisi film pertama dan terakhir, dipakai untuk membuat cursor
+    pub first: Option<Anchor>,
+    pub last: Option<Anchor>,
+    pub has_previous: bool,
+    pub has_more: bool,
+}
+
+impl ListingRange {
+    // Satu halaman upstream apa adanya
+    pub fn from_page(movies: Vec<Movie>, page: u32) -> Self {
+        let anchor = |movie: &Movie| Anchor { id: movie.id.clone(), page };
+        ListingRange {
+            first: movies.first().map(anchor),
+            last: movies.last().map(anchor),
+            has_previous: page > 1,
+            has_more: !movies.is_empty(),
+            total_items: None,
+            movies,
+        }
+    }
+}
+
+// Mengambil film ke-offset sampai offset+limit dari halaman-halaman upstream yang mencakupnya.
+// Film yang bergeser ke halaman berikutnya karena ada film baru muncul dua kali dan dibuang,
+// kekurangan akibat deduplikasi diisi dari halaman tambahan.
+pub async fn load_range(scraper: &FilmapikScraper, listing: &Listing, offset: u32, limit: u32) -> Result<ListingRange, AppError> {
+    let limit = limit.max(1);
+    let first = offset / UPSTREAM_PER_PAGE + 1;
+    let last = (offset + limit - 1) / UPSTREAM_PER_PAGE + 1;
+    let skip = (offset % UPSTREAM_PER_PAGE) as usize;
+
+    info!("Mengambil listing {} offset {} limit {} dari halaman {}-{}", listing, offset, limit, first, last);
+
+    let mut stitched = Stitched::starting_at(first);
+    stitched.fetch(scraper, listing, last - first + 1).await?;
+    stitched.fill(scraper, listing, skip + limit as usize).await?;
+
+    let total_items = stitched
+        .exhausted
+        .then(|| (first - 1) * UPSTREAM_PER_PAGE + stitched.movies.len() as u32);
+
+    Ok(stitched.slice(skip, limit as usize, offset > 0, total_items))
+}
+
+// Mengambil `limit` film setelah atau sebelum penanda. Penanda dicari di halaman asalnya dan halaman
+// berikutnya karena film baru di awal listing mendorong film lama ke halaman berikutnya, sehingga
+// film yang sudah dilihat klien tidak muncul lagi. Jika penanda sudah hilang dari listing,
+// pengambilan dilanjutkan dari batas halaman asalnya.
+pub async fn load_from_anchor(
+    scraper: &FilmapikScraper,
+    listing: &Listing,
+    anchor: &Anchor,
+    direction: Direction,
+    limit: u32,
+) -> Result<ListingRange, AppError> {
+    let limit = limit.max(1) as usize;
+    let first = match direction {
+        Direction::After => anchor.page,
+        Direction::Before => anchor.page.saturating_sub((limit as u32).div_ceil(UPSTREAM_PER_PAGE)).max(1),
+    };
+
+    info!("Mengambil listing {} {:?} {} (halaman {})", listing, direction, anchor.id, anchor.page);
+
+    let mut stitched = Stitched::starting_at(first);
+    stitched.fetch(scraper, listing, anchor.page - first + 2).await?;
+    if stitched.position(&anchor.id).is_none() && stitched.can_continue() {
+        stitched.fetch(scraper, listing, 1).await?;
+    }
+
+    let position = stitched.position(&anchor.id);
+    if position.is_none() {
+        warn!("Penanda {} tidak ditemukan di listing {}", anchor.id, listing);
+    }
+
+    match direction {
+        Direction::After => {
+            let start = position
+                .map(|i| i + 1)
+                .unwrap_or_else(|| stitched.pages.iter().position(|p| *p > anchor.page).unwrap_or(stitched.movies.len()));
+            stitched.fill(scraper, listing, start + limit).await?;
+            Ok(stitched.slice(start, limit, true, None))
+        },
+        Direction::Before => {
+            let end = position
+                .unwrap_or_else(|| stitched.pages.iter().position(|p| *p >= anchor.page).unwrap_or(stitched.movies.len()));
+            let start = end.saturating_sub(limit);
+            Ok(stitched.slice(start, end - start, start > 0 || first > 1, None))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(id: &str, title: &str, year: Option<i32>, rating: Option<f32>) -> Movie {
+        Movie {
+            id: id.to_string(),
+            title: title.to_string(),
+            poster: String::new(),
+            year,
+            rating,
+            quality: None,
+            genres: Vec::new(),
+            url: String::new(),
+            provider: String::new(),
+            views: None,
+            description: None,
+            duration: None,
+            country: None,
+            director: None,
+            expanded: None,
+        }
+    }
+
+    fn ids(movies: &[Movie]) -> Vec<&str> {
+        movies.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn sorts_stably_with_unknown_values_last() {
+        let mut movies = vec![
+            movie("a", "beta", Some(2021), Some(7.0)),
+            movie("b", "Alpha", None, None),
+            movie("c", "gamma", Some(2023), Some(7.0)),
+            movie("d", "delta", Some(2021), Some(8.5)),
+        ];
+
+        ListingSort::new(SortField::Rating, None).sort(&mut movies);
+        assert_eq!(ids(&movies), ["d", "a", "c", "b"]);
+
+        ListingSort::new(SortField::Rating, Some(SortOrder::Asc)).sort(&mut movies);
+        assert_eq!(ids(&movies), ["a", "c", "d", "b"]);
+
+        ListingSort::new(SortField::Year, Some(SortOrder::Asc)).sort(&mut movies);
+        assert_eq!(ids(&movies), ["a", "d", "c", "b"]);
+
+        ListingSort::new(SortField::Title, None).sort(&mut movies);
+        assert_eq!(ids(&movies), ["b", "a", "d", "c"]);
+
+        // Tanpa data views urutan sebelumnya dipertahankan
+        ListingSort::new(SortField::Views, None).sort(&mut movies);
+        assert_eq!(ids(&movies), ["b", "a", "d", "c"]);
+    }
+}
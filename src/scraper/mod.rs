@@ -1,3 +1,16 @@
 pub mod filmapik;
+pub mod domain;
+pub mod challenge;
+pub mod selectors;
+pub mod parser;
+pub mod canary;
+pub mod snapshot;
+pub mod vcr;
+pub mod provider;
+pub mod wordpress;
+pub mod discover;
+pub mod listing;
+pub mod random;
 
-pub use filmapik::FilmapikScraper; 
\ No newline at end of file
+pub use filmapik::{FilmapikScraper, Site, UpstreamPage};
+pub use provider::{ProviderRegistry, SourceProvider};
@@ -0,0 +1,448 @@
+use crate::models::movie::{Movie, MovieDetail, ParseWarning, Person};
+use crate::scraper::selectors::{self, Selectors};
+use scraper::{ElementRef, Html};
+
+// Jumlah field halaman detail yang diperiksa untuk menghitung skor kelengkapan
+const DETAIL_EXPECTED_FIELDS: usize = 15;
+
+// Hasil parse halaman listing
+#[derive(Debug)]
+pub struct MovieListing {
+    pub movies: Vec<Movie>,
+    // Apakah container daftar film (#gmr-main-load) ditemukan di halaman
+    pub has_container: bool,
+    // Nomor halaman terakhir dari navigasi pagination, None jika navigasi tidak ada
+    pub last_page: Option<u32>,
+    // Judul halaman (h1.page-title) tanpa label arsip, None jika tidak ada atau kosong
+    pub heading: Option<String>,
+}
+
+// Mengambil ID film dari URL (segmen path terakhir yang tidak kosong)
+fn id_from_url(url: &str) -> String {
+    url.split('/').rfind(|s| !s.is_empty()).unwrap_or_default().to_string()
+}
+
+// Apakah teks metadata diawali salah satu label
+fn has_label(text: &str, labels: &[String]) -> bool {
+    labels.iter().any(|label| text.starts_with(label.as_str()))
+}
+
+// Mengambil nilai setelah label metadata, misalnya "Tahun: 2023" -> "2023"
+fn label_value(text: &str, labels: &[String]) -> Option<String> {
+    labels
+        .iter()
+        .find_map(|label| text.strip_prefix(label.as_str()))
+        .map(|value| value.trim().to_string())
+}
+
+// Tahun diambil dari kelompok kurung terakhir di akhir judul, misalnya
+// "Film (Director's Cut) (2020)" -> 2020
+pub fn year_from_title(title: &str) -> Option<i32> {
+    let (_, group) = title.trim_end().rsplit_once('(')?;
+    group.strip_suffix(')')?.trim().parse::<i32>().ok()
+}
+
+// Jumlah dilihat diambil dari teks setelah penanda terakhir, misalnya "... oleh admin dilihat 12,345".
+// Pemisah ribuan bisa berupa ',' maupun '.'.
+fn views_from_meta(meta_text: &str, marker: &str) -> Option<u32> {
+    let (_, views_str) = meta_text.rsplit_once(marker)?;
+    views_str
+        .trim()
+        .replace([',', '.'], "")
+        .parse::<u32>()
+        .ok()
+}
+
+// Link orang (pemeran/sutradara) di dalam elemen, link tanpa teks diabaikan
+fn persons_in(selectors: &Selectors, element: ElementRef) -> Vec<Person> {
+    selectors
+        .detail_person_link
+        .all_in(element)
+        .into_iter()
+        .filter_map(|link| {
+            let url = link.value().attr("href")?.trim().to_string();
+            let name = link.text().collect::<String>().trim().to_string();
+            let id = id_from_url(&url);
+            (!name.is_empty() && !id.is_empty()).then_some(Person { id, name, url })
+        })
+        .collect()
+}
+
+pub fn parse_movie_list(html: &str) -> MovieListing {
+    parse_movie_list_with(&selectors::current(), html)
+}
+
+pub fn parse_movie_list_with(selectors: &Selectors, html: &str) -> MovieListing {
+    let document = Html::parse_document(html);
+    
+    let movies = selectors
+        .listing_item
+        .all_in_doc(&document)
+        .into_iter()
+        .filter_map(|movie_element| parse_movie_item(selectors, movie_element))
+        .collect();
+    
+    let has_container = selectors.listing_container.first_in_doc(&document).is_some();
+    
+    // Nomor halaman terbesar di navigasi, link "Next" dan "..." diabaikan
+    let last_page = selectors
+        .listing_pagination
+        .all_in_doc(&document)
+        .into_iter()
+        .filter_map(|link| link.text().collect::<String>().trim().replace([',', '.'], "").parse::<u32>().ok())
+        .max();
+    
+    // Label arsip ("Cast: Lee Jung-jae") dibuang, titik dua di bagian lain judul tetap dipertahankan
+    let heading = selectors
+        .listing_heading
+        .first_in_doc(&document)
+        .map(|element| element.text().collect::<String>().trim().to_string())
+        .map(|heading| label_value(&heading, &selectors.labels.archive).unwrap_or(heading))
+        .filter(|heading| !heading.is_empty());
+    
+    MovieListing { movies, has_container, last_page, heading }
+}
+
+fn parse_movie_item(selectors: &Selectors, movie_element: ElementRef) -> Option<Movie> {
+    let title_element = selectors.listing_title.first_in(movie_element)?;
+    
+    let title = title_element.text().next().unwrap_or_default().trim().to_string();
+    let url = title_element.value().attr("href").unwrap_or_default().to_string();
+    let id = id_from_url(&url);
+    
+    let poster = selectors
+        .listing_poster
+        .first_in(movie_element)
+        .and_then(|el| el.value().attr("src"))
+        .unwrap_or_default()
+        .to_string();
+    
+    // Ambil rating (opsional)
+    let rating = selectors
+        .listing_rating
+        .first_in(movie_element)
+        .and_then(|el| el.text().next())
+        .and_then(|s| {
+            // Filter string untuk mendapatkan angka rating
+            let s = s.trim();
+            let s = s.trim_start_matches([' ', '\t']);
+            s.parse::<f32>().ok()
+        });
+    
+    // Ambil kualitas (opsional)
+    let quality = selectors
+        .listing_quality
+        .first_in(movie_element)
+        .and_then(|el| el.text().next())
+        .map(|s| s.trim().to_string());
+    
+    // Ekstrak tahun dari judul film
+    let year = year_from_title(&title);
+    
+    // Ambil genre
+    let mut genres = Vec::new();
+    for genre_element in selectors.listing_genre.all_in(movie_element) {
+        if let Some(genre_text) = genre_element.text().next() {
+            genres.push(genre_text.trim().to_string());
+        }
+    }
+    
+    Some(Movie {
+        id,
+        title,
+        poster,
+        year,
+        rating,
+        quality,
+        genres,
+        url,
+        // Diisi oleh scraper sesuai situs asal halaman
+        provider: String::new(),
+        views: None,
+        description: None,
+        duration: None,
+        country: None,
+        director: None,
+        expanded: None,
+    })
+}
+
+pub fn parse_movie_detail(html: &str, movie_id: &str, url: &str) -> MovieDetail {
+    parse_movie_detail_with(&selectors::current(), html, movie_id, url)
+}
+
+pub fn parse_movie_detail_with(selectors: &Selectors, html: &str, movie_id: &str, url: &str) -> MovieDetail {
+    let document = Html::parse_document(html);
+    
+    // Extract title
+    let parsed_title = selectors
+        .detail_title
+        .first_in_doc(&document)
+        .and_then(|el| el.text().next())
+        .map(|s| s.trim().to_string());
+    
+    // Extract poster
+    let poster = selectors
+        .detail_poster
+        .first_in_doc(&document)
+        .and_then(|el| el.value().attr("src"))
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    
+    // Extract description
+    let description = selectors
+        .detail_description
+        .first_in_doc(&document)
+        .map(|el| {
+            let desc_text = el.text().collect::<Vec<_>>().join(" ");
+            desc_text.trim().to_string()
+        });
+    
+    // Extract rating
+    let rating = selectors
+        .detail_rating
+        .first_in_doc(&document)
+        .and_then(|el| el.text().next())
+        .and_then(|s| {
+            s.trim().parse::<f32>().ok()
+        });
+    
+    // Extract watch URL dari iframe
+    let watch_url = selectors
+        .detail_iframe
+        .first_in_doc(&document)
+        .and_then(|el| el.value().attr("src"))
+        .map(|s| s.to_string());
+    
+    // Default values
+    let rating_count: Option<u32> = None;
+    let mut quality: Option<String> = None;
+    let mut year: Option<i32> = None;
+    let mut year_raw: Option<String> = None;
+    let mut duration: Option<String> = None;
+    let mut country: Option<String> = None;
+    let mut release_date: Option<String> = None;
+    let mut language: Option<String> = None;
+    let mut director: Option<String> = None;
+    let mut directors = Vec::new();
+    let mut views: Option<u32> = None;
+    
+    // Extract metadata information
+    let labels = &selectors.labels;
+    for meta_element in selectors.detail_meta.all_in_doc(&document) {
+        let meta_text = meta_element.text().collect::<Vec<_>>().join(" ");
+        let meta_text = meta_text.trim();
+        
+        if has_label(meta_text, &labels.views) && meta_text.contains(labels.views_marker.as_str()) {
+            // Extract views
+            views = views_from_meta(meta_text, &labels.views_marker);
+        } else if let Some(value) = label_value(meta_text, &labels.quality) {
+            quality = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.year) {
+            year = value.parse::<i32>().ok();
+            year_raw = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.duration) {
+            duration = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.country) {
+            country = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.release_date) {
+            release_date = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.language) {
+            language = Some(value);
+        } else if let Some(value) = label_value(meta_text, &labels.director) {
+            director = Some(value);
+            directors = persons_in(selectors, meta_element);
+        }
+    }
+    
+    // Extract genres
+    let mut genres = Vec::new();
+    for genre_element in selectors.detail_genre.all_in_doc(&document) {
+        if let Some(genre_text) = genre_element.text().next() {
+            let genre_text = genre_text.trim();
+            if !genre_text.is_empty() {
+                genres.push(genre_text.to_string());
+            }
+        }
+    }
+    
+    // Extract actors
+    let mut actors = Vec::new();
+    let mut cast = Vec::new();
+    for actor_element in selectors.detail_cast.all_in_doc(&document) {
+        if let Some(actor_text) = actor_element.text().next() {
+            let actor_text = actor_text.trim();
+            if !actor_text.is_empty() {
+                actors.push(actor_text.to_string());
+            }
+        }
+        cast.extend(persons_in(selectors, actor_element));
+    }
+    
+    // Extract related movies
+    let mut related_movies = Vec::new();
+    for related_element in selectors.detail_related.all_in_doc(&document) {
+        let related_title = selectors
+            .related_title
+            .first_in(related_element)
+            .and_then(|el| el.text().next())
+            .map(|s| s.trim().to_string());
+        
+        let related_url = selectors
+            .related_title
+            .first_in(related_element)
+            .and_then(|el| el.value().attr("href"))
+            .map(|s| s.to_string());
+        
+        let related_poster = selectors
+            .related_poster
+            .first_in(related_element)
+            .and_then(|el| el.value().attr("src"))
+            .map(|s| s.to_string());
+        
+        if let (Some(title), Some(url)) = (related_title, related_url) {
+            related_movies.push(Movie {
+                id: id_from_url(&url),
+                title,
+                poster: related_poster.unwrap_or_default(),
+                year: None,
+                rating: None,
+                quality: None,
+                genres: Vec::new(),
+                url,
+                provider: String::new(),
+                views: None,
+                description: None,
+                duration: None,
+                country: None,
+                director: None,
+                expanded: None,
+            });
+        }
+    }
+    
+    // Catat field yang diharapkan tapi tidak ditemukan, agar selector yang rusak bisa dibedakan
+    // dari film yang memang tidak memiliki data tersebut
+    let mut parse_warnings = Vec::new();
+    let mut expect = |field: &str, found: bool, message: &str| {
+        if !found {
+            parse_warnings.push(ParseWarning {
+                field: field.to_string(),
+                message: message.to_string(),
+            });
+        }
+    };
+    
+    expect("title", parsed_title.as_deref().is_some_and(|t| !t.is_empty()), "elemen judul tidak ditemukan");
+    expect("poster", !poster.is_empty(), "elemen poster tidak ditemukan");
+    expect("description", description.as_deref().is_some_and(|d| !d.is_empty()), "elemen deskripsi tidak ditemukan");
+    expect("rating", rating.is_some(), "rating tidak ditemukan atau bukan angka");
+    expect("watch_url", watch_url.is_some(), "iframe stream tidak ditemukan");
+    expect("genres", !genres.is_empty(), "genre tidak ditemukan");
+    expect("actors", !actors.is_empty(), "daftar pemeran tidak ditemukan");
+    expect("views", views.is_some(), "baris metadata jumlah dilihat tidak ditemukan");
+    expect("quality", quality.is_some(), "baris metadata kualitas tidak ditemukan");
+    match &year_raw {
+        Some(raw) => expect("year", year.is_some(), &format!("tahun tidak valid: {}", raw)),
+        None => expect("year", false, "baris metadata tahun tidak ditemukan"),
+    }
+    expect("duration", duration.is_some(), "baris metadata durasi tidak ditemukan");
+    expect("country", country.is_some(), "baris metadata negara tidak ditemukan");
+    expect("release_date", release_date.is_some(), "baris metadata tanggal rilis tidak ditemukan");
+    expect("language", language.is_some(), "baris metadata bahasa tidak ditemukan");
+    expect("director", director.is_some(), "baris metadata sutradara tidak ditemukan");
+    
+    let completeness = 1.0 - parse_warnings.len() as f32 / DETAIL_EXPECTED_FIELDS as f32;
+    
+    MovieDetail {
+        id: movie_id.to_string(),
+        title: parsed_title.unwrap_or_else(|| "Unknown Title".to_string()),
+        poster,
+        rating,
+        rating_count,
+        description,
+        views,
+        genres,
+        quality,
+        year,
+        duration,
+        country,
+        release_date,
+        language,
+        director,
+        actors,
+        directors,
+        cast,
+        url: url.to_string(),
+        watch_url,
+        related_movies,
+        provider: String::new(),
+        completeness,
+        parse_warnings: Some(parse_warnings),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETAIL_HTML: &str = include_str!("../../tests/fixtures/detail.html");
+    const LISTING_HTML: &str = include_str!("../../tests/fixtures/listing.html");
+
+    #[test]
+    fn listing_reads_last_page_from_pagination() {
+        let listing = parse_movie_list(LISTING_HTML);
+        assert_eq!(listing.movies.len(), 24);
+        assert_eq!(listing.last_page, Some(412));
+
+        let start = LISTING_HTML.find("<div class=\"pagination\">").unwrap();
+        let without_pagination = format!("{}</div></div>", &LISTING_HTML[..start]);
+        assert_eq!(parse_movie_list(&without_pagination).last_page, None);
+
+        // Teks <ul class="page-numbers"> ("12") tidak boleh terbaca sebagai nomor halaman
+        let two_pages = format!(
+            "{}<div class=\"pagination\"><ul class=\"page-numbers\"><li><span class=\"page-numbers current\">1</span></li><li><a class=\"page-numbers\" href=\"/page/2/\">2</a></li></ul></div>",
+            &LISTING_HTML[..start]
+        );
+        assert_eq!(parse_movie_list(&two_pages).last_page, Some(2));
+    }
+
+    #[test]
+    fn listing_reads_page_heading() {
+        assert_eq!(parse_movie_list(LISTING_HTML).heading.as_deref(), Some("Film Terbaru"));
+
+        let person = LISTING_HTML.replace(">Film Terbaru</h1>", ">  Lee Jung-jae </h1>");
+        assert_eq!(parse_movie_list(&person).heading.as_deref(), Some("Lee Jung-jae"));
+
+        let empty = LISTING_HTML.replace(">Film Terbaru</h1>", "></h1>");
+        assert_eq!(parse_movie_list(&empty).heading, None);
+
+        // Hanya label arsip yang dibuang, titik dua di dalam nama tetap utuh
+        let labelled = LISTING_HTML.replace(">Film Terbaru</h1>", ">Director: Bong Joon-ho</h1>");
+        assert_eq!(parse_movie_list(&labelled).heading.as_deref(), Some("Bong Joon-ho"));
+        let colon = LISTING_HTML.replace(">Film Terbaru</h1>", ">Will.i.am: Live</h1>");
+        assert_eq!(parse_movie_list(&colon).heading.as_deref(), Some("Will.i.am: Live"));
+    }
+
+    #[test]
+    fn complete_detail_has_no_warnings() {
+        let detail = parse_movie_detail(DETAIL_HTML, "paayum-oli-nee-yenakku-2023", "http://localhost/paayum-oli-nee-yenakku-2023/");
+        assert_eq!(detail.parse_warnings.as_deref().map(|w| w.len()), Some(0));
+        assert_eq!(detail.completeness, 1.0);
+        
+        assert_eq!(detail.directors.len(), 1);
+        assert_eq!(detail.directors[0].id, "karthik-adwait");
+        assert_eq!(detail.directors[0].name, "Karthik Adwait");
+        assert_eq!(detail.cast.len(), detail.actors.len());
+        assert_eq!(detail.cast[0].url, "http://194.102.105.201/cast/vikram-prabhu/");
+    }
+
+    #[test]
+    fn missing_fields_are_reported() {
+        let html = DETAIL_HTML.replace("Tahun:", "Year").replace("<iframe", "<div");
+        let detail = parse_movie_detail(&html, "film", "http://localhost/film/");
+        let fields: Vec<_> = detail.parse_warnings.unwrap().into_iter().map(|w| w.field).collect();
+        assert_eq!(fields, vec!["watch_url", "year"]);
+        assert!(detail.completeness < 1.0);
+    }
+}
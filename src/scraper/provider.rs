@@ -0,0 +1,293 @@
+use crate::config::EXTRA_PROVIDERS;
+use crate::errors::AppError;
+use crate::models::movie::{CatalogPage, Country, Genre, Movie, MovieDetail, PersonMovies, TaxonomyTerm};
+use crate::scraper::filmapik::{FilmapikScraper, Site};
+use crate::scraper::discover::{self, DiscoverFilters, DiscoverResult, DiscoverSort};
+use crate::scraper::listing::{self, Anchor, Direction, Listing, ListingRange};
+use crate::scraper::random::{self, RandomFilters};
+use crate::scraper::wordpress::Taxonomy;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+
+// Sumber data film. Setiap situs streaming diimplementasikan sebagai satu provider,
+// ID film (slug) hanya unik di dalam provider yang sama.
+#[async_trait]
+pub trait SourceProvider: Send + Sync {
+    // ID provider yang dipakai di query `?provider=` dan di field `provider` pada film
+    fn id(&self) -> &str;
+    fn name(&self) -> &str;
+    // Base URL situs tanpa trailing slash
+    fn base_url(&self) -> String;
+
+    async fn latest(&self, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn popular(&self, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn by_genre(&self, genre_id: &str, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn by_country(&self, country_id: &str, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn by_year(&self, year: i32, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn by_quality(&self, quality: &str, page: u32) -> Result<Vec<Movie>, AppError>;
+    // Gabungan halaman 1..=pages dari listing, dipakai untuk mengurutkan lintas halaman
+    async fn window(&self, listing: &Listing, pages: u32) -> Result<Vec<Movie>, AppError>;
+    // Film ke-offset sampai offset+limit dari listing, bisa mencakup beberapa halaman upstream
+    async fn range(&self, listing: &Listing, offset: u32, limit: u32) -> Result<ListingRange, AppError>;
+    // Film setelah atau sebelum film penanda dari cursor
+    async fn around(&self, listing: &Listing, anchor: &Anchor, direction: Direction, limit: u32) -> Result<ListingRange, AppError>;
+    async fn detail(&self, movie_id: &str) -> Result<MovieDetail, AppError>;
+    async fn person(&self, person_id: &str, page: u32) -> Result<PersonMovies, AppError>;
+    async fn search(&self, query: &str, page: u32) -> Result<Vec<Movie>, AppError>;
+    async fn discover(&self, filters: &DiscoverFilters, sort: DiscoverSort, page: u32) -> Result<DiscoverResult, AppError>;
+    // Detail satu film acak yang sesuai filter, seed yang sama memberikan film yang sama
+    async fn random(&self, filters: &RandomFilters, seed: Option<u64>) -> Result<MovieDetail, AppError>;
+    async fn genres(&self) -> Result<Vec<Genre>, AppError>;
+    async fn countries(&self) -> Result<Vec<Country>, AppError>;
+    async fn terms(&self, taxonomy: Taxonomy) -> Result<Vec<TaxonomyTerm>, AppError>;
+    async fn catalog(&self, page: u32) -> Result<CatalogPage, AppError>;
+}
+
+#[async_trait]
+impl SourceProvider for FilmapikScraper {
+    fn id(&self) -> &str {
+        &self.site().id
+    }
+
+    fn name(&self) -> &str {
+        &self.site().name
+    }
+
+    fn base_url(&self) -> String {
+        FilmapikScraper::base_url(self)
+    }
+
+    async fn latest(&self, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_latest_movies(page).await
+    }
+
+    async fn popular(&self, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_popular_movies(page).await
+    }
+
+    async fn by_genre(&self, genre_id: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_movies_by_genre(genre_id, page).await
+    }
+
+    async fn by_country(&self, country_id: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_movies_by_country(country_id, page).await
+    }
+
+    async fn by_year(&self, year: i32, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_movies_by_year(year, page).await
+    }
+
+    async fn by_quality(&self, quality: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.get_movies_by_quality(quality, page).await
+    }
+
+    async fn window(&self, listing: &Listing, pages: u32) -> Result<Vec<Movie>, AppError> {
+        listing::load_window(self, listing, pages).await
+    }
+
+    async fn range(&self, listing: &Listing, offset: u32, limit: u32) -> Result<ListingRange, AppError> {
+        listing::load_range(self, listing, offset, limit).await
+    }
+
+    async fn around(&self, listing: &Listing, anchor: &Anchor, direction: Direction, limit: u32) -> Result<ListingRange, AppError> {
+        listing::load_from_anchor(self, listing, anchor, direction, limit).await
+    }
+
+    async fn detail(&self, movie_id: &str) -> Result<MovieDetail, AppError> {
+        self.get_movie_detail(movie_id).await
+    }
+
+    async fn person(&self, person_id: &str, page: u32) -> Result<PersonMovies, AppError> {
+        self.get_person_movies(person_id, page).await
+    }
+
+    async fn search(&self, query: &str, page: u32) -> Result<Vec<Movie>, AppError> {
+        self.search_movies(query, page).await
+    }
+
+    async fn discover(&self, filters: &DiscoverFilters, sort: DiscoverSort, page: u32) -> Result<DiscoverResult, AppError> {
+        discover::discover(self, filters, sort, page).await
+    }
+
+    async fn random(&self, filters: &RandomFilters, seed: Option<u64>) -> Result<MovieDetail, AppError> {
+        random::random_movie(self, filters, seed).await
+    }
+
+    async fn genres(&self) -> Result<Vec<Genre>, AppError> {
+        self.get_genres().await
+    }
+
+    async fn countries(&self) -> Result<Vec<Country>, AppError> {
+        self.get_countries().await
+    }
+
+    async fn terms(&self, taxonomy: Taxonomy) -> Result<Vec<TaxonomyTerm>, AppError> {
+        self.get_terms(taxonomy).await
+    }
+
+    async fn catalog(&self, page: u32) -> Result<CatalogPage, AppError> {
+        self.get_catalog(page).await
+    }
+}
+
+// Informasi provider yang ditampilkan di GET /api/providers
+#[derive(Debug, Serialize, Clone)]
+pub struct ProviderInfo {
+    pub id: String,
+    pub name: String,
+    pub base_url: String,
+    pub default: bool,
+}
+
+// Daftar provider yang tersedia, provider pertama adalah default
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn SourceProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new(default: Arc<dyn SourceProvider>) -> Self {
+        Self { providers: vec![default] }
+    }
+
+    pub fn register(&mut self, provider: Arc<dyn SourceProvider>) -> Result<(), String> {
+        if self.providers.iter().any(|p| p.id() == provider.id()) {
+            return Err(format!("Provider {} sudah terdaftar", provider.id()));
+        }
+        self.providers.push(provider);
+        Ok(())
+    }
+
+    // FilmApik sebagai provider default ditambah situs dari EXTRA_PROVIDERS.
+    // Situs tambahan memakai client dan cache yang sama dengan scraper FilmApik.
+    pub fn from_config(scraper: &FilmapikScraper) -> Result<Self, String> {
+        let mut registry = Self::new(Arc::new(scraper.clone()));
+        for site in parse_extra_providers(&EXTRA_PROVIDERS)? {
+            registry.register(Arc::new(scraper.with_site(site)))?;
+        }
+        Ok(registry)
+    }
+
+    // Mengambil provider berdasarkan ID, None berarti provider default
+    pub fn get(&self, id: Option<&str>) -> Result<Arc<dyn SourceProvider>, AppError> {
+        let Some(id) = id.map(str::trim).filter(|id| !id.is_empty()) else {
+            return Ok(self.default_provider());
+        };
+
+        self.providers
+            .iter()
+            .find(|p| p.id().eq_ignore_ascii_case(id))
+            .cloned()
+            .ok_or_else(|| {
+                let ids: Vec<&str> = self.providers.iter().map(|p| p.id()).collect();
+                AppError::invalid_parameter(
+                    "provider",
+                    format!("Provider tidak dikenal: {} (pilihan: {})", id, ids.join(", ")),
+                )
+            })
+    }
+
+    pub fn default_provider(&self) -> Arc<dyn SourceProvider> {
+        self.providers[0].clone()
+    }
+
+    pub fn list(&self) -> Vec<ProviderInfo> {
+        self.providers
+            .iter()
+            .enumerate()
+            .map(|(i, p)| ProviderInfo {
+                id: p.id().to_string(),
+                name: p.name().to_string(),
+                base_url: p.base_url(),
+                default: i == 0,
+            })
+            .collect()
+    }
+}
+
+// Format EXTRA_PROVIDERS: daftar "id=url" dipisahkan koma, misalnya
+// "filmapik2=https://filmapik2.example,layarkaca=https://layarkaca.example"
+fn parse_extra_providers(raw: &str) -> Result<Vec<Site>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, url) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("EXTRA_PROVIDERS tidak valid: {} (format: id=url)", entry))?;
+            let id = id.trim().to_ascii_lowercase();
+            let url = url.trim().trim_end_matches('/');
+
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                return Err(format!("ID provider tidak valid: {} (hanya huruf, angka, '-' dan '_')", id));
+            }
+            match url::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {},
+                _ => return Err(format!("URL provider {} tidak valid: {}", id, url)),
+            }
+
+            Ok(Site {
+                name: id.clone(),
+                id,
+                base_url: Some(url.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_extra_providers() {
+        let sites = parse_extra_providers(" Sister=https://sister.example/ , ,other=http://10.0.0.1:8080").unwrap();
+        assert_eq!(sites.len(), 2);
+        assert_eq!(sites[0].id, "sister");
+        assert_eq!(sites[0].base_url.as_deref(), Some("https://sister.example"));
+        assert_eq!(sites[1].base_url.as_deref(), Some("http://10.0.0.1:8080"));
+
+        assert!(parse_extra_providers("").unwrap().is_empty());
+        for raw in ["sister", "sis/ter=https://a.example", "sister=ftp://a.example", "sister=bukan-url"] {
+            assert!(parse_extra_providers(raw).is_err(), "input {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn registry_resolves_default_and_rejects_unknown() {
+        let scraper = FilmapikScraper::new();
+        let mut registry = ProviderRegistry::new(Arc::new(scraper.clone()));
+        let sister = Site {
+            id: "sister".to_string(),
+            name: "Sister".to_string(),
+            base_url: Some("https://sister.example".to_string()),
+        };
+        registry.register(Arc::new(scraper.with_site(sister.clone()))).unwrap();
+        assert!(registry.register(Arc::new(scraper.with_site(sister))).is_err());
+
+        assert_eq!(registry.get(None).unwrap().id(), "filmapik");
+        assert_eq!(registry.get(Some("")).unwrap().id(), "filmapik");
+        assert_eq!(registry.get(Some("Sister")).unwrap().base_url(), "https://sister.example");
+        let err = registry.get(Some("unknown")).err().unwrap();
+        assert_eq!(err.code(), crate::errors::ErrorCode::InvalidParameter);
+    }
+
+    #[tokio::test]
+    async fn cached_term_lists_follow_base_url() {
+        let scraper = FilmapikScraper::new();
+        let site = |base_url: &str| Site {
+            id: "mirror".to_string(),
+            name: "Mirror".to_string(),
+            base_url: Some(base_url.to_string()),
+        };
+
+        // Cache dipakai bersama, seperti situs yang sama sebelum dan sesudah pindah domain
+        let old = scraper.with_site(site("http://lama.example"));
+        assert!(old.genres().await.unwrap()[0].url.starts_with("http://lama.example/"));
+        assert!(old.countries().await.unwrap()[0].url.starts_with("http://lama.example/"));
+
+        let moved = scraper.with_site(site("http://baru.example"));
+        assert!(moved.genres().await.unwrap()[0].url.starts_with("http://baru.example/"));
+        assert!(moved.countries().await.unwrap()[0].url.starts_with("http://baru.example/"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::config::{MAX_PAGE, RANDOM_MAX_PAGES};
+use crate::errors::AppError;
+use crate::models::movie::{Movie, MovieDetail};
+use crate::scraper::discover::slugify;
+use crate::scraper::filmapik::FilmapikScraper;
+use crate::scraper::listing::Listing;
+use futures::stream::{self, StreamExt};
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// Jumlah halaman acak pertama yang diambil satu per satu, halaman berikutnya diambil bersamaan
+const RANDOM_MAX_ATTEMPTS: usize = 5;
+
+// Jumlah halaman yang diambil bersamaan setelah halaman acak pertama tidak berisi film yang cocok
+const RANDOM_SCAN_CONCURRENCY: usize = 4;
+
+// Filter film acak, semua opsional dan digabung dengan AND
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RandomFilters {
+    pub genre: Option<String>,
+    pub country: Option<String>,
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    pub min_rating: Option<f32>,
+}
+
+impl RandomFilters {
+    // Listing sumber, urutannya sama dengan discover: negara (tidak ada di item listing sehingga harus
+    // menjadi sumber), tahun tunggal, genre. Rentang tahun tertutup tanpa facet lain memakai listing setiap
+    // tahun dalam rentang dengan urutan acak; listing berikutnya hanya dipakai jika listing sebelumnya tidak
+    // berisi film yang cocok.
+    fn sources(&self, rng: &mut StdRng) -> Vec<Listing> {
+        if let Some(country) = &self.country {
+            return vec![Listing::Country(country.clone())];
+        }
+        match (self.year_from, self.year_to, &self.genre) {
+            (Some(from), Some(to), _) if from == to => vec![Listing::Year(from)],
+            (_, _, Some(genre)) => vec![Listing::Genre(genre.clone())],
+            (Some(from), Some(to), None) => {
+                let mut years: Vec<Listing> = (from..=to).map(Listing::Year).collect();
+                years.shuffle(rng);
+                years
+            },
+            _ => vec![Listing::Latest],
+        }
+    }
+
+    // Apakah film memenuhi filter yang bisa diperiksa dari item listing, facet sumber tidak diperiksa ulang.
+    // Film tanpa genre, tahun atau rating tidak lolos filter yang bersangkutan.
+    fn matches(&self, movie: &Movie, source: &Listing) -> bool {
+        let genre = matches!(source, Listing::Genre(_))
+            || self.genre.as_ref().is_none_or(|genre| movie.genres.iter().any(|g| slugify(g) == *genre));
+        let year = matches!(source, Listing::Year(_))
+            || (self.year_from.is_none_or(|from| movie.year.is_some_and(|year| year >= from))
+                && self.year_to.is_none_or(|to| movie.year.is_some_and(|year| year <= to)));
+        let rating = self.min_rating.is_none_or(|min| movie.rating.is_some_and(|rating| rating >= min));
+
+        genre && year && rating
+    }
+}
+
+// Film yang cocok dari satu halaman listing. Halaman yang tidak ditemukan dianggap kosong
+// karena listing bisa menyusut sejak jumlah halamannya dicatat.
+async fn candidates(scraper: &FilmapikScraper, filters: &RandomFilters, source: &Listing, page: u32) -> Result<Vec<Movie>, AppError> {
+    match source.load(scraper, page).await {
+        Ok(movies) => Ok(movies.into_iter().filter(|movie| filters.matches(movie, source)).collect()),
+        Err(AppError::UpstreamNotFound { url }) => {
+            warn!("Halaman {} untuk film acak tidak ditemukan: {}", page, url);
+            Ok(Vec::new())
+        },
+        Err(e) => Err(e),
+    }
+}
+
+// Film acak sesuai filter. Halaman listing sumber (jumlahnya dari navigasi pagination) diambil dengan urutan
+// acak tanpa pengulangan, lalu satu film yang cocok dipilih dari halaman pertama yang berisi film cocok dan
+// detailnya diambil. Seluruh listing sumber berbagi batas RANDOM_MAX_PAGES halaman (termasuk halaman pertama
+// yang dibaca untuk jumlah halaman), NotFound dikembalikan jika batas habis tanpa film yang cocok.
+// Dengan seed yang sama dan isi upstream yang sama hasilnya selalu sama.
+pub async fn random_movie(scraper: &FilmapikScraper, filters: &RandomFilters, seed: Option<u64>) -> Result<MovieDetail, AppError> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut budget = *RANDOM_MAX_PAGES as usize;
+
+    for source in filters.sources(&mut rng) {
+        if budget == 0 {
+            info!("Batas {} halaman film acak habis", *RANDOM_MAX_PAGES);
+            break;
+        }
+        budget -= 1;
+        let last_page = scraper.last_page(&source).await?.unwrap_or(1).clamp(1, *MAX_PAGE);
+        info!("Memilih film acak dari listing {} ({} halaman)", source, last_page);
+
+        let mut pages: Vec<u32> = (1..=last_page).collect();
+        pages.shuffle(&mut rng);
+        pages.truncate(budget);
+        budget -= pages.len();
+
+        let rest = pages.split_off(pages.len().min(RANDOM_MAX_ATTEMPTS));
+        for page in pages {
+            if let Some(movie) = candidates(scraper, filters, &source, page).await?.choose(&mut rng) {
+                return scraper.get_movie_detail(&movie.id).await;
+            }
+        }
+
+        if rest.is_empty() {
+            continue;
+        }
+        info!("Halaman acak listing {} tidak berisi film yang cocok, memeriksa {} halaman lainnya", source, rest.len());
+
+        let mut pages = stream::iter(rest)
+            .map(|page| candidates(scraper, filters, &source, page))
+            .buffered(RANDOM_SCAN_CONCURRENCY);
+        while let Some(movies) = pages.next().await {
+            if let Some(movie) = movies?.choose(&mut rng) {
+                return scraper.get_movie_detail(&movie.id).await;
+            }
+        }
+    }
+
+    Err(AppError::NotFoundError("Tidak ada film yang cocok dengan filter".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(genres: &[&str], year: Option<i32>, rating: Option<f32>) -> Movie {
+        Movie {
+            id: "film".to_string(),
+            title: "Film".to_string(),
+            poster: String::new(),
+            year,
+            rating,
+            quality: None,
+            genres: genres.iter().map(|g| g.to_string()).collect(),
+            url: String::new(),
+            provider: String::new(),
+            views: None,
+            description: None,
+            duration: None,
+            country: None,
+            director: None,
+            expanded: None,
+        }
+    }
+
+    #[test]
+    fn sources_and_filters_follow_facets() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let filters = RandomFilters {
+            genre: Some("science-fiction".to_string()),
+            country: Some("korea".to_string()),
+            year_from: Some(2020),
+            min_rating: Some(7.0),
+            ..Default::default()
+        };
+        let source = Listing::Country("korea".to_string());
+        assert_eq!(filters.sources(&mut rng), vec![source.clone()]);
+        assert!(filters.matches(&movie(&["Science Fiction"], Some(2021), Some(7.5)), &source));
+        assert!(!filters.matches(&movie(&["Drama"], Some(2021), Some(7.5)), &source));
+        assert!(!filters.matches(&movie(&["Science Fiction"], None, Some(7.5)), &source));
+        assert!(!filters.matches(&movie(&["Science Fiction"], Some(2021), None), &source));
+
+        // Tahun tunggal lebih selektif dari genre, genre tetap diperiksa dari item listing
+        let year_and_genre = RandomFilters {
+            genre: Some("drama".to_string()),
+            year_from: Some(2021),
+            year_to: Some(2021),
+            ..Default::default()
+        };
+        assert_eq!(year_and_genre.sources(&mut rng), vec![Listing::Year(2021)]);
+        assert!(!year_and_genre.matches(&movie(&["Action"], Some(2021), None), &Listing::Year(2021)));
+
+        let years = RandomFilters { year_from: Some(2001), year_to: Some(2003), ..Default::default() };
+        let mut sources = years.sources(&mut rng);
+        sources.sort_by_key(|source| source.key());
+        assert_eq!(sources, vec![Listing::Year(2001), Listing::Year(2002), Listing::Year(2003)]);
+    }
+}
@@ -0,0 +1,369 @@
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::info;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::sync::{Arc, RwLock};
+
+// Versi format konfigurasi selector yang didukung
+const SUPPORTED_VERSION: u32 = 1;
+
+// Konfigurasi default yang di-embed ke binary
+pub const DEFAULT_SELECTORS_TOML: &str = include_str!("../../config/selectors.toml");
+
+// Daftar selector yang dicoba berurutan, selector pertama yang menemukan elemen dipakai
+pub struct SelectorChain(Vec<Selector>);
+
+impl SelectorChain {
+    fn compile(field: &str, css_list: &[String]) -> Result<Self, String> {
+        if css_list.is_empty() {
+            return Err(format!("{}: daftar selector kosong", field));
+        }
+
+        css_list
+            .iter()
+            .map(|css| {
+                Selector::parse(css).map_err(|e| format!("{}: selector \"{}\" tidak valid: {}", field, css, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(SelectorChain)
+    }
+
+    pub fn first_in_doc<'a>(&self, document: &'a Html) -> Option<ElementRef<'a>> {
+        self.0.iter().find_map(|selector| document.select(selector).next())
+    }
+
+    pub fn first_in<'a>(&self, element: ElementRef<'a>) -> Option<ElementRef<'a>> {
+        self.0.iter().find_map(|selector| element.select(selector).next())
+    }
+
+    pub fn all_in_doc<'a>(&self, document: &'a Html) -> Vec<ElementRef<'a>> {
+        self.0
+            .iter()
+            .map(|selector| document.select(selector).collect::<Vec<_>>())
+            .find(|elements| !elements.is_empty())
+            .unwrap_or_default()
+    }
+
+    pub fn all_in<'a>(&self, element: ElementRef<'a>) -> Vec<ElementRef<'a>> {
+        self.0
+            .iter()
+            .map(|selector| element.select(selector).collect::<Vec<_>>())
+            .find(|elements| !elements.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+// Format file konfigurasi (TOML)
+#[derive(Debug, Deserialize)]
+struct SelectorsFile {
+    version: u32,
+    listing: ListingConfig,
+    detail: DetailConfig,
+    labels: MetaLabels,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingConfig {
+    container: Vec<String>,
+    item: Vec<String>,
+    title: Vec<String>,
+    poster: Vec<String>,
+    quality: Vec<String>,
+    rating: Vec<String>,
+    genre: Vec<String>,
+    // Nomor halaman di navigasi pagination (item list, bukan <ul> yang kelasnya sama), opsional agar konfigurasi lama tetap valid
+    #[serde(default = "default_pagination")]
+    pagination: Vec<String>,
+    // Judul halaman arsip (nama genre, pemeran, dll), opsional seperti pagination
+    #[serde(default = "default_heading")]
+    heading: Vec<String>,
+}
+
+fn default_pagination() -> Vec<String> {
+    vec![".pagination li > .page-numbers".to_string()]
+}
+
+fn default_heading() -> Vec<String> {
+    vec!["h1.page-title".to_string(), ".page-header h1".to_string()]
+}
+
+#[derive(Debug, Deserialize)]
+struct DetailConfig {
+    title: Vec<String>,
+    poster: Vec<String>,
+    description: Vec<String>,
+    rating: Vec<String>,
+    meta: Vec<String>,
+    genre: Vec<String>,
+    iframe: Vec<String>,
+    cast: Vec<String>,
+    // Link halaman orang di dalam item pemeran dan baris sutradara, opsional agar konfigurasi lama tetap valid
+    #[serde(default = "default_person_link")]
+    person_link: Vec<String>,
+    related: Vec<String>,
+    related_title: Vec<String>,
+    related_poster: Vec<String>,
+}
+
+fn default_person_link() -> Vec<String> {
+    vec!["a[href]".to_string()]
+}
+
+// Label baris metadata pada halaman detail ("Kualitas:", "Tahun:", dll)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MetaLabels {
+    pub views: Vec<String>,
+    pub views_marker: String,
+    pub quality: Vec<String>,
+    pub year: Vec<String>,
+    pub duration: Vec<String>,
+    pub country: Vec<String>,
+    pub release_date: Vec<String>,
+    pub language: Vec<String>,
+    pub director: Vec<String>,
+    // Label judul halaman arsip WordPress ("Cast: Nama"), opsional agar konfigurasi lama tetap valid
+    #[serde(default = "default_archive_labels")]
+    pub archive: Vec<String>,
+}
+
+fn default_archive_labels() -> Vec<String> {
+    ["Cast:", "Pemeran:", "Director:", "Sutradara:"].iter().map(|label| label.to_string()).collect()
+}
+
+impl MetaLabels {
+    fn validate(&self) -> Result<(), String> {
+        let groups = [
+            ("labels.views", &self.views),
+            ("labels.quality", &self.quality),
+            ("labels.year", &self.year),
+            ("labels.duration", &self.duration),
+            ("labels.country", &self.country),
+            ("labels.release_date", &self.release_date),
+            ("labels.language", &self.language),
+            ("labels.director", &self.director),
+            ("labels.archive", &self.archive),
+        ];
+
+        for (field, labels) in groups {
+            if labels.is_empty() || labels.iter().any(|label| label.trim().is_empty()) {
+                return Err(format!("{}: label tidak boleh kosong", field));
+            }
+        }
+
+        if self.views_marker.trim().is_empty() {
+            return Err("labels.views_marker: tidak boleh kosong".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// Semua selector yang dipakai parser, sudah dikompilasi dan divalidasi
+pub struct Selectors {
+    pub version: u32,
+
+    // Halaman listing (terbaru, populer, genre, negara)
+    pub listing_container: SelectorChain,
+    pub listing_item: SelectorChain,
+    pub listing_title: SelectorChain,
+    pub listing_poster: SelectorChain,
+    pub listing_quality: SelectorChain,
+    pub listing_rating: SelectorChain,
+    pub listing_genre: SelectorChain,
+    pub listing_pagination: SelectorChain,
+    pub listing_heading: SelectorChain,
+
+    // Halaman detail film
+    pub detail_title: SelectorChain,
+    pub detail_poster: SelectorChain,
+    pub detail_description: SelectorChain,
+    pub detail_rating: SelectorChain,
+    pub detail_meta: SelectorChain,
+    pub detail_genre: SelectorChain,
+    pub detail_iframe: SelectorChain,
+    pub detail_cast: SelectorChain,
+    pub detail_person_link: SelectorChain,
+    pub detail_related: SelectorChain,
+    pub related_title: SelectorChain,
+    pub related_poster: SelectorChain,
+
+    pub labels: MetaLabels,
+}
+
+impl Selectors {
+    // Parse dan validasi konfigurasi selector dari string TOML
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let file: SelectorsFile = toml::from_str(content).map_err(|e| format!("format TOML tidak valid: {}", e))?;
+
+        if file.version != SUPPORTED_VERSION {
+            return Err(format!(
+                "versi konfigurasi {} tidak didukung (didukung: {})",
+                file.version, SUPPORTED_VERSION
+            ));
+        }
+
+        file.labels.validate()?;
+
+        let listing = &file.listing;
+        let detail = &file.detail;
+
+        Ok(Self {
+            version: file.version,
+
+            listing_container: SelectorChain::compile("listing.container", &listing.container)?,
+            listing_item: SelectorChain::compile("listing.item", &listing.item)?,
+            listing_title: SelectorChain::compile("listing.title", &listing.title)?,
+            listing_poster: SelectorChain::compile("listing.poster", &listing.poster)?,
+            listing_quality: SelectorChain::compile("listing.quality", &listing.quality)?,
+            listing_rating: SelectorChain::compile("listing.rating", &listing.rating)?,
+            listing_genre: SelectorChain::compile("listing.genre", &listing.genre)?,
+            listing_pagination: SelectorChain::compile("listing.pagination", &listing.pagination)?,
+            listing_heading: SelectorChain::compile("listing.heading", &listing.heading)?,
+
+            detail_title: SelectorChain::compile("detail.title", &detail.title)?,
+            detail_poster: SelectorChain::compile("detail.poster", &detail.poster)?,
+            detail_description: SelectorChain::compile("detail.description", &detail.description)?,
+            detail_rating: SelectorChain::compile("detail.rating", &detail.rating)?,
+            detail_meta: SelectorChain::compile("detail.meta", &detail.meta)?,
+            detail_genre: SelectorChain::compile("detail.genre", &detail.genre)?,
+            detail_iframe: SelectorChain::compile("detail.iframe", &detail.iframe)?,
+            detail_cast: SelectorChain::compile("detail.cast", &detail.cast)?,
+            detail_person_link: SelectorChain::compile("detail.person_link", &detail.person_link)?,
+            detail_related: SelectorChain::compile("detail.related", &detail.related)?,
+            related_title: SelectorChain::compile("detail.related_title", &detail.related_title)?,
+            related_poster: SelectorChain::compile("detail.related_poster", &detail.related_poster)?,
+
+            labels: file.labels,
+        })
+    }
+
+    // Konfigurasi default yang di-embed ke binary
+    pub fn load_default() -> Result<Self, String> {
+        Self::from_toml(DEFAULT_SELECTORS_TOML)
+    }
+}
+
+// Informasi konfigurasi selector yang sedang aktif
+#[derive(Debug, Serialize, Clone)]
+pub struct SelectorsInfo {
+    pub version: u32,
+    pub source: String,
+    pub loaded_at: DateTime<Utc>,
+}
+
+struct ActiveSelectors {
+    selectors: Arc<Selectors>,
+    info: SelectorsInfo,
+}
+
+lazy_static! {
+    // Path file konfigurasi selector, jika tidak diset memakai konfigurasi default
+    pub static ref SELECTORS_CONFIG: Option<String> = env::var("SELECTORS_CONFIG").ok().filter(|s| !s.is_empty());
+
+    static ref ACTIVE: RwLock<ActiveSelectors> = RwLock::new(
+        load().unwrap_or_else(|e| panic!("Gagal memuat konfigurasi selector: {}", e))
+    );
+}
+
+fn load() -> Result<ActiveSelectors, String> {
+    let (content, source) = match SELECTORS_CONFIG.as_ref() {
+        Some(path) => (
+            fs::read_to_string(path).map_err(|e| format!("gagal membaca {}: {}", path, e))?,
+            path.clone(),
+        ),
+        None => (DEFAULT_SELECTORS_TOML.to_string(), "default".to_string()),
+    };
+
+    let selectors = Selectors::from_toml(&content).map_err(|e| format!("{}: {}", source, e))?;
+    let info = SelectorsInfo {
+        version: selectors.version,
+        source,
+        loaded_at: Utc::now(),
+    };
+
+    Ok(ActiveSelectors {
+        selectors: Arc::new(selectors),
+        info,
+    })
+}
+
+// Dipanggil saat startup agar konfigurasi selector yang tidak valid langsung menghentikan aplikasi
+pub fn init() {
+    lazy_static::initialize(&ACTIVE);
+    info!("Konfigurasi selector dimuat: {:?}", info());
+}
+
+// Selector yang sedang aktif
+pub fn current() -> Arc<Selectors> {
+    ACTIVE
+        .read()
+        .map(|active| active.selectors.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().selectors.clone())
+}
+
+pub fn info() -> SelectorsInfo {
+    ACTIVE
+        .read()
+        .map(|active| active.info.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().info.clone())
+}
+
+// Memuat ulang konfigurasi selector. Jika konfigurasi baru tidak valid, konfigurasi lama tetap dipakai.
+pub fn reload() -> Result<SelectorsInfo, String> {
+    let loaded = load()?;
+    let info = loaded.info.clone();
+
+    match ACTIVE.write() {
+        Ok(mut active) => *active = loaded,
+        Err(poisoned) => *poisoned.into_inner() = loaded,
+    }
+
+    info!("Konfigurasi selector dimuat ulang: {:?}", info);
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_error(content: &str) -> String {
+        match Selectors::from_toml(content) {
+            Ok(_) => panic!("konfigurasi seharusnya ditolak"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        let selectors = Selectors::load_default().unwrap();
+        assert_eq!(selectors.version, SUPPORTED_VERSION);
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let content = DEFAULT_SELECTORS_TOML.replace("version = 1", "version = 99");
+        assert!(load_error(&content).contains("versi"));
+    }
+
+    #[test]
+    fn rejects_invalid_selector() {
+        let content = DEFAULT_SELECTORS_TOML.replace("container = [\"#gmr-main-load\"]", "container = [\"##\"]");
+        assert!(load_error(&content).contains("listing.container"));
+    }
+
+    #[test]
+    fn rejects_empty_chain() {
+        let content = DEFAULT_SELECTORS_TOML.replace("genre = [\".gmr-movie-on a\"]\n\n[detail]", "genre = []\n\n[detail]");
+        assert!(load_error(&content).contains("listing.genre"));
+    }
+
+    #[test]
+    fn chain_falls_back_to_next_selector() {
+        let chain = SelectorChain::compile("test", &[".missing".to_string(), "h1".to_string()]).unwrap();
+        let document = Html::parse_document("<h1>Judul</h1>");
+        assert_eq!(chain.first_in_doc(&document).unwrap().inner_html(), "Judul");
+    }
+}
@@ -0,0 +1,255 @@
+use crate::config::{SNAPSHOT_DIR, SNAPSHOT_MAX_AGE_SECS, SNAPSHOT_MAX_BYTES};
+use crate::scraper::filmapik::UpstreamPage;
+use chrono::{DateTime, Duration, Utc};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Panjang maksimum potongan path URL yang dipakai di ID snapshot
+const MAX_ID_SLUG_LEN: usize = 60;
+
+// Header upstream yang disimpan di snapshot. Snapshot bisa dibaca lewat endpoint admin,
+// jadi cookie (termasuk cookie clearance anti-bot) dan header lain tidak pernah ditulis.
+const DIAGNOSTIC_HEADERS: &[&str] = &["content-type", "server", "cf-ray", "retry-after"];
+const DIAGNOSTIC_HEADER_PREFIXES: &[&str] = &["x-wp-"];
+
+fn is_diagnostic_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    DIAGNOSTIC_HEADERS.contains(&name.as_str()) || DIAGNOSTIC_HEADER_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+// Membuang header di luar daftar, juga dipakai saat membaca snapshot lama yang masih menyimpan semua header
+fn scrub_headers(mut meta: SnapshotMeta) -> SnapshotMeta {
+    meta.headers.retain(|name, _| is_diagnostic_header(name));
+    meta
+}
+
+// Metadata snapshot, disimpan sebagai {id}.json di samping {id}.html
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SnapshotMeta {
+    pub id: String,
+    pub kind: String,
+    pub reason: String,
+    pub url: String,
+    pub final_url: String,
+    pub status: u16,
+    pub referer: String,
+    pub headers: BTreeMap<String, String>,
+    pub fetched_at: DateTime<Utc>,
+    pub captured_at: DateTime<Utc>,
+    pub size: u64,
+}
+
+// Potongan path URL yang aman dipakai sebagai bagian nama file
+pub(crate) fn url_slug(url: &str) -> String {
+    let path = url::Url::parse(url).map(|u| u.path().to_string()).unwrap_or_default();
+    let slug: String = path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let slug: String = slug
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .take(MAX_ID_SLUG_LEN)
+        .collect();
+
+    if slug.is_empty() { "home".to_string() } else { slug }
+}
+
+// Membuat ID snapshot yang aman dipakai sebagai nama file, diawali timestamp agar urut secara kronologis
+fn snapshot_id(kind: &str, url: &str, captured_at: DateTime<Utc>) -> String {
+    format!("{}-{}-{}", captured_at.format("%Y%m%dT%H%M%S%3f"), kind, url_slug(url))
+}
+
+fn write_snapshot(dir: &Path, page: &UpstreamPage, kind: &str, reason: &str) -> std::io::Result<SnapshotMeta> {
+    fs::create_dir_all(dir)?;
+
+    let captured_at = Utc::now();
+    let meta = SnapshotMeta {
+        id: snapshot_id(kind, &page.url, captured_at),
+        kind: kind.to_string(),
+        reason: reason.to_string(),
+        url: page.url.clone(),
+        final_url: page.final_url.clone(),
+        status: page.status,
+        referer: page.referer.clone(),
+        headers: page
+            .headers
+            .iter()
+            .filter(|(name, _)| is_diagnostic_header(name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect(),
+        fetched_at: page.fetched_at,
+        captured_at,
+        size: page.body.len() as u64,
+    };
+
+    // HTML disimpan apa adanya agar bisa langsung dipakai sebagai fixture test
+    fs::write(dir.join(format!("{}.html", meta.id)), &page.body)?;
+    fs::write(dir.join(format!("{}.json", meta.id)), serde_json::to_vec_pretty(&meta)?)?;
+
+    Ok(meta)
+}
+
+// Membaca metadata semua snapshot di direktori, terbaru di awal
+fn read_all(dir: &Path) -> Vec<SnapshotMeta> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut snapshots: Vec<SnapshotMeta> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read(&path).ok())
+        .filter_map(|content| serde_json::from_slice(&content).ok())
+        .map(scrub_headers)
+        .collect();
+
+    snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+    snapshots
+}
+
+fn remove_snapshot(dir: &Path, id: &str) {
+    for ext in ["html", "json"] {
+        let path = dir.join(format!("{}.{}", id, ext));
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Gagal menghapus snapshot {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Menghapus snapshot yang lebih tua dari max_age, lalu snapshot terlama sampai total ukuran di bawah max_bytes
+fn rotate(dir: &Path, max_bytes: u64, max_age: Duration) {
+    let cutoff = Utc::now() - max_age;
+    let mut total: u64 = 0;
+
+    for meta in read_all(dir) {
+        if meta.captured_at < cutoff || total + meta.size > max_bytes {
+            info!("Menghapus snapshot lama {}", meta.id);
+            remove_snapshot(dir, &meta.id);
+        } else {
+            total += meta.size;
+        }
+    }
+}
+
+// Menyimpan snapshot HTML dari upstream. Mengembalikan None jika snapshot dinonaktifkan atau gagal disimpan.
+pub fn capture(page: &UpstreamPage, kind: &str, reason: &str) -> Option<SnapshotMeta> {
+    if *SNAPSHOT_MAX_BYTES == 0 {
+        return None;
+    }
+
+    let dir = Path::new(SNAPSHOT_DIR.as_str());
+    match write_snapshot(dir, page, kind, reason) {
+        Ok(meta) => {
+            warn!("Snapshot HTML {} disimpan untuk {}: {}", meta.id, page.url, reason);
+            rotate(dir, *SNAPSHOT_MAX_BYTES, Duration::seconds(*SNAPSHOT_MAX_AGE_SECS as i64));
+            Some(meta)
+        },
+        Err(e) => {
+            error!("Gagal menyimpan snapshot HTML untuk {}: {}", page.url, e);
+            None
+        }
+    }
+}
+
+// Menyimpan snapshot di thread pool blocking tanpa menunggu hasilnya
+pub fn capture_in_background(page: UpstreamPage, kind: &'static str, reason: String) {
+    tokio::task::spawn_blocking(move || capture(&page, kind, &reason));
+}
+
+// Daftar snapshot yang tersimpan, terbaru di awal
+pub fn list() -> Vec<SnapshotMeta> {
+    read_all(Path::new(SNAPSHOT_DIR.as_str()))
+}
+
+pub fn get(id: &str) -> Option<SnapshotMeta> {
+    let content = fs::read(Path::new(SNAPSHOT_DIR.as_str()).join(format!("{}.json", id))).ok()?;
+    serde_json::from_slice(&content).ok().map(scrub_headers)
+}
+
+// Path file HTML snapshot
+pub fn html_path(id: &str) -> PathBuf {
+    Path::new(SNAPSHOT_DIR.as_str()).join(format!("{}.html", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(url: &str, body: &str) -> UpstreamPage {
+        UpstreamPage {
+            url: url.to_string(),
+            final_url: url.to_string(),
+            status: 200,
+            referer: String::new(),
+            headers: BTreeMap::new(),
+            fetched_at: Utc::now(),
+            body: body.to_string(),
+        }
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("filmapik-snapshot-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn id_is_safe_file_name() {
+        let id = snapshot_id("detail", "http://localhost/paayum-oli/?p=1", Utc::now());
+        assert!(id.ends_with("-detail-paayum-oli"));
+        assert!(id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    }
+
+    #[test]
+    fn only_diagnostic_headers_are_stored() {
+        let dir = test_dir("headers");
+        let mut upstream = page("http://localhost/film/", "<html></html>");
+        for (name, value) in [
+            ("content-type", "text/html"),
+            ("set-cookie", "cf_clearance=rahasia"),
+            ("cf-ray", "abc-SIN"),
+            ("x-wp-total", "12"),
+            ("authorization", "Bearer rahasia"),
+        ] {
+            upstream.headers.insert(name.to_string(), value.to_string());
+        }
+
+        let meta = write_snapshot(&dir, &upstream, "detail", "test").unwrap();
+        let stored: Vec<&str> = meta.headers.keys().map(String::as_str).collect();
+        assert_eq!(stored, vec!["cf-ray", "content-type", "x-wp-total"]);
+        assert!(!fs::read_to_string(dir.join(format!("{}.json", meta.id))).unwrap().contains("rahasia"));
+
+        // Snapshot lama yang masih berisi cookie tidak ikut mengirimkannya
+        let mut old = meta.clone();
+        old.headers.insert("set-cookie".to_string(), "cf_clearance=rahasia".to_string());
+        fs::write(dir.join(format!("{}.json", old.id)), serde_json::to_vec(&old).unwrap()).unwrap();
+        assert!(!read_all(&dir)[0].headers.contains_key("set-cookie"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rotation_keeps_newest_within_size_limit() {
+        let dir = test_dir("rotate");
+        for i in 0..3 {
+            write_snapshot(&dir, &page(&format!("http://localhost/film-{}/", i), "0123456789"), "detail", "test").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        rotate(&dir, 25, Duration::days(1));
+
+        let remaining: Vec<_> = read_all(&dir).into_iter().map(|m| m.url).collect();
+        assert_eq!(remaining, vec!["http://localhost/film-2/", "http://localhost/film-1/"]);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 4);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+use crate::config::{CASSETTE_DIR, UPSTREAM_MODE};
+use crate::errors::AppError;
+use crate::scraper::snapshot;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::{error, info};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use url::Url;
+
+// Mode client HTTP ke upstream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // Request dikirim langsung ke upstream
+    Live,
+    // Request dikirim ke upstream dan setiap response disimpan ke file cassette
+    Record,
+    // Response diambil dari file cassette, request yang tidak memiliki cassette menghasilkan error
+    Replay,
+}
+
+impl Mode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "live" => Ok(Mode::Live),
+            "record" => Ok(Mode::Record),
+            "replay" => Ok(Mode::Replay),
+            other => Err(format!("UPSTREAM_MODE tidak valid: {} (pilihan: live, record, replay)", other)),
+        }
+    }
+}
+
+struct VcrState {
+    mode: Mode,
+    dir: PathBuf,
+}
+
+lazy_static! {
+    static ref STATE: RwLock<VcrState> = RwLock::new(VcrState {
+        mode: Mode::parse(&UPSTREAM_MODE).unwrap_or_else(|e| panic!("{}", e)),
+        dir: PathBuf::from(CASSETTE_DIR.as_str()),
+    });
+}
+
+// Dipanggil saat startup agar nilai UPSTREAM_MODE yang tidak valid langsung menghentikan aplikasi
+pub fn init() {
+    let (mode, dir) = current();
+    if mode != Mode::Live {
+        info!("Client upstream berjalan dalam mode {:?} dengan cassette di {}", mode, dir.display());
+    }
+}
+
+pub fn current() -> (Mode, PathBuf) {
+    let state = STATE.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    (state.mode, state.dir.clone())
+}
+
+// Mengganti mode dan direktori cassette saat runtime (dipakai oleh test)
+pub fn set(mode: Mode, dir: impl Into<PathBuf>) {
+    let mut state = STATE.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.mode = mode;
+    state.dir = dir.into();
+}
+
+// Response upstream yang sudah dibaca penuh, baik dari jaringan maupun dari cassette
+#[derive(Debug)]
+pub struct Response {
+    pub status: StatusCode,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+// Format file cassette: satu file per request (method + URL)
+#[derive(Debug, Serialize, Deserialize)]
+struct Cassette {
+    request: CassetteRequest,
+    response: CassetteResponse,
+    recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteRequest {
+    method: String,
+    url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteResponse {
+    status: u16,
+    url: String,
+    headers: Vec<(String, String)>,
+    // Body teks disimpan apa adanya agar cassette mudah dibaca dan diedit,
+    // body biner (misalnya konten dari proxy video) disimpan sebagai base64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+// FNV-1a 64-bit, hasilnya stabil antar versi Rust sehingga nama file cassette tidak berubah
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn cassette_path(dir: &Path, method: &str, url: &str) -> PathBuf {
+    let key = format!("{} {}", method, url);
+    dir.join(format!(
+        "{}-{}-{:016x}.json",
+        method.to_ascii_lowercase(),
+        snapshot::url_slug(url),
+        fnv1a(&key)
+    ))
+}
+
+fn to_cassette(method: &str, request_url: &str, response: &Response) -> Cassette {
+    let (body, body_base64) = match std::str::from_utf8(&response.body) {
+        Ok(text) => (Some(text.to_string()), None),
+        Err(_) => (None, Some(BASE64.encode(&response.body))),
+    };
+
+    Cassette {
+        request: CassetteRequest {
+            method: method.to_string(),
+            url: request_url.to_string(),
+        },
+        response: CassetteResponse {
+            status: response.status.as_u16(),
+            url: response.url.to_string(),
+            headers: response
+                .headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+                .collect(),
+            body,
+            body_base64,
+        },
+        recorded_at: Utc::now(),
+    }
+}
+
+fn from_cassette(cassette: Cassette) -> Result<Response, String> {
+    let response = cassette.response;
+
+    let status = StatusCode::from_u16(response.status).map_err(|e| e.to_string())?;
+    let url = Url::parse(&response.url).map_err(|e| e.to_string())?;
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in response.headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| e.to_string())?;
+        let value = HeaderValue::from_str(&value).map_err(|e| e.to_string())?;
+        headers.append(name, value);
+    }
+
+    let body = match (response.body, response.body_base64) {
+        (Some(text), _) => Bytes::from(text),
+        (None, Some(encoded)) => Bytes::from(BASE64.decode(encoded).map_err(|e| e.to_string())?),
+        (None, None) => Bytes::new(),
+    };
+
+    Ok(Response { status, url, headers, body })
+}
+
+async fn replay(dir: &Path, method: &str, url: &str) -> Result<Response, AppError> {
+    let path = cassette_path(dir, method, url);
+
+    let content = tokio::fs::read(&path).await.map_err(|_| {
+        error!("Cassette tidak ditemukan untuk {} {} ({})", method, url, path.display());
+        AppError::InternalError(format!("Mode replay: tidak ada cassette untuk {} {}", method, url))
+    })?;
+
+    serde_json::from_slice::<Cassette>(&content)
+        .map_err(|e| e.to_string())
+        .and_then(from_cassette)
+        .map_err(|e| AppError::InternalError(format!("Cassette {} tidak valid: {}", path.display(), e)))
+}
+
+async fn record(dir: &Path, method: &str, url: &str, response: &Response) {
+    let path = cassette_path(dir, method, url);
+    let result = match serde_json::to_vec_pretty(&to_cassette(method, url, response)) {
+        Ok(content) => match tokio::fs::create_dir_all(dir).await {
+            Ok(()) => tokio::fs::write(&path, content).await,
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e.into()),
+    };
+
+    match result {
+        Ok(()) => info!("Cassette disimpan: {}", path.display()),
+        Err(e) => error!("Gagal menyimpan cassette {}: {}", path.display(), e),
+    }
+}
+
+// Mengirim request ke upstream sesuai mode aktif dan membaca seluruh body response
+pub async fn send(client: &Client, request: RequestBuilder) -> Result<Response, AppError> {
+    let request = request.build()?;
+    let method = request.method().to_string();
+    let url = request.url().to_string();
+
+    let (mode, dir) = current();
+    if mode == Mode::Replay {
+        return replay(&dir, &method, &url).await;
+    }
+
+    let response = client.execute(request).await?;
+    let status = response.status();
+    let final_url = response.url().clone();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+
+    let response = Response {
+        status,
+        url: final_url,
+        headers,
+        body,
+    };
+
+    if mode == Mode::Record {
+        record(&dir, &method, &url, &response).await;
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &[u8]) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/html"));
+        headers.append("set-cookie", HeaderValue::from_static("a=1"));
+        headers.append("set-cookie", HeaderValue::from_static("b=2"));
+
+        Response {
+            status: StatusCode::OK,
+            url: Url::parse("http://localhost/film/").unwrap(),
+            headers,
+            body: Bytes::copy_from_slice(body),
+        }
+    }
+
+    #[test]
+    fn cassette_round_trip_keeps_text_and_binary_bodies() {
+        for body in [b"<html>film</html>".as_slice(), &[0xff, 0x00, 0xfe]] {
+            let cassette = to_cassette("GET", "http://localhost/film/", &response(body));
+            let json = serde_json::to_string(&cassette).unwrap();
+            let restored = from_cassette(serde_json::from_str(&json).unwrap()).unwrap();
+
+            assert_eq!(&restored.body[..], body);
+            assert_eq!(restored.status, StatusCode::OK);
+            assert_eq!(restored.headers.get_all("set-cookie").iter().count(), 2);
+        }
+    }
+
+    #[test]
+    fn cassette_path_is_stable_and_distinct() {
+        let dir = Path::new("cassettes");
+        let a = cassette_path(dir, "GET", "http://localhost/page/2/");
+        let b = cassette_path(dir, "GET", "http://localhost/page/2/?x=1");
+
+        assert_eq!(a, cassette_path(dir, "GET", "http://localhost/page/2/"));
+        assert_ne!(a, b);
+        assert!(a.to_string_lossy().starts_with("cassettes/get-page-2-"));
+    }
+
+    #[test]
+    fn parses_modes() {
+        assert_eq!(Mode::parse("").unwrap(), Mode::Live);
+        assert_eq!(Mode::parse("Replay").unwrap(), Mode::Replay);
+        assert!(Mode::parse("offline").is_err());
+    }
+}
@@ -0,0 +1,434 @@
+use crate::config::CATALOG_SOURCE;
+use crate::errors::AppError;
+use crate::models::movie::{CatalogEntry, CatalogPage, TaxonomyTerm};
+use crate::scraper::filmapik::{run_blocking, FilmapikScraper, UpstreamPage};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::RwLock;
+
+// Jumlah item per halaman REST API, 100 adalah batas maksimum WordPress
+pub const REST_PER_PAGE: u32 = 100;
+
+// Batas halaman REST API yang diambil untuk satu taksonomi (100 term per halaman)
+const MAX_TERM_PAGES: u32 = 20;
+
+// Batas file sitemap yang diambil untuk satu daftar
+const MAX_SITEMAP_FILES: usize = 50;
+
+// Lokasi sitemap index: bawaan WordPress 5.5+ lalu Yoast/Rank Math
+const SITEMAP_INDEXES: [&str; 2] = ["wp-sitemap.xml", "sitemap_index.xml"];
+
+// Sumber daftar genre, negara dan katalog film
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogSource {
+    // Daftar statis dan scraping halaman HTML
+    Html,
+    // REST API WordPress, lalu sitemap XML, lalu HTML jika keduanya tidak tersedia
+    Wordpress,
+}
+
+impl CatalogSource {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "html" => Ok(CatalogSource::Html),
+            "wordpress" | "wp" => Ok(CatalogSource::Wordpress),
+            other => Err(format!("CATALOG_SOURCE tidak valid: {} (pilihan: html, wordpress)", other)),
+        }
+    }
+}
+
+lazy_static! {
+    static ref SOURCE: RwLock<CatalogSource> =
+        RwLock::new(CatalogSource::parse(&CATALOG_SOURCE).unwrap_or_else(|e| panic!("{}", e)));
+
+    static ref SITEMAP_ENTRY_RE: Regex = Regex::new(r"(?s)<(url|sitemap)>(.*?)</(?:url|sitemap)>").unwrap();
+    static ref SITEMAP_LOC_RE: Regex = Regex::new(r"(?s)<loc>(.*?)</loc>").unwrap();
+    static ref SITEMAP_LASTMOD_RE: Regex = Regex::new(r"(?s)<lastmod>(.*?)</lastmod>").unwrap();
+}
+
+// Dipanggil saat startup agar nilai CATALOG_SOURCE yang tidak valid langsung menghentikan aplikasi
+pub fn init() {
+    let source = current();
+    if source != CatalogSource::Html {
+        info!("Daftar genre, negara dan katalog diambil dari REST API/sitemap WordPress");
+    }
+}
+
+pub fn current() -> CatalogSource {
+    *SOURCE.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Mengganti sumber data saat runtime (dipakai oleh test)
+pub fn set(source: CatalogSource) {
+    *SOURCE.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = source;
+}
+
+// Taksonomi WordPress yang didaftarkan tema Muvipro
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Taxonomy {
+    Genre,
+    Country,
+    Year,
+    Quality,
+    Cast,
+    Director,
+}
+
+impl Taxonomy {
+    // Nama taksonomi di WordPress, dipakai juga oleh nama file sitemap
+    pub fn wp_name(self) -> &'static str {
+        match self {
+            Taxonomy::Genre => "category",
+            Taxonomy::Country => "muvicountry",
+            Taxonomy::Year => "muviyear",
+            Taxonomy::Quality => "muviquality",
+            Taxonomy::Cast => "muvicast",
+            Taxonomy::Director => "muvidirector",
+        }
+    }
+
+    // Endpoint REST API di bawah /wp-json/wp/v2/
+    pub fn rest_base(self) -> &'static str {
+        match self {
+            Taxonomy::Genre => "categories",
+            other => other.wp_name(),
+        }
+    }
+
+    // Prefix URL halaman arsip di situs, misalnya /genre/action/
+    pub fn path(self) -> &'static str {
+        match self {
+            Taxonomy::Genre => "genre",
+            Taxonomy::Country => "country",
+            Taxonomy::Year => "year",
+            Taxonomy::Quality => "quality",
+            Taxonomy::Cast => "cast",
+            Taxonomy::Director => "director",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WpTerm {
+    slug: String,
+    name: String,
+    #[serde(default)]
+    count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WpPost {
+    slug: String,
+    title: WpRendered,
+    #[serde(default)]
+    modified_gmt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WpRendered {
+    rendered: String,
+}
+
+// Satu <url> atau <sitemap> di file sitemap XML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<String>,
+}
+
+// Mengambil semua term sebuah taksonomi, dari REST API atau dari sitemap jika REST API tidak tersedia
+pub(crate) async fn fetch_terms(scraper: &FilmapikScraper, taxonomy: Taxonomy) -> Result<Vec<TaxonomyTerm>, AppError> {
+    match fetch_rest_terms(scraper, taxonomy).await {
+        Ok(terms) if !terms.is_empty() => return Ok(terms),
+        Ok(_) => warn!("REST API {} tidak mengembalikan term {}", scraper.site().name, taxonomy.wp_name()),
+        Err(e) => warn!("REST API {} tidak tersedia untuk {}: {}", scraper.site().name, taxonomy.wp_name(), e),
+    }
+
+    let base = scraper.base_url();
+    let prefixes = [format!("wp-sitemap-taxonomies-{}-", taxonomy.wp_name()), format!("{}-sitemap", taxonomy.wp_name())];
+    let entries = fetch_sitemap_urls(scraper, &prefixes).await?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| slug_from_url(&entry.loc))
+        .map(|slug| TaxonomyTerm {
+            name: name_from_slug(&slug),
+            url: format!("{}/{}/{}/", base, taxonomy.path(), slug),
+            id: slug,
+            count: None,
+        })
+        .collect())
+}
+
+async fn fetch_rest_terms(scraper: &FilmapikScraper, taxonomy: Taxonomy) -> Result<Vec<TaxonomyTerm>, AppError> {
+    let base = scraper.base_url();
+    let mut terms = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let url = format!(
+            "{}/wp-json/wp/v2/{}?per_page={}&page={}&hide_empty=true&_fields=slug,name,count",
+            base,
+            taxonomy.rest_base(),
+            REST_PER_PAGE,
+            page
+        );
+        let upstream_page = scraper.fetch_page(&url, format!("{}/", base)).await?;
+        let total_pages = header_u32(&upstream_page, "x-wp-totalpages");
+
+        let parsed = run_blocking(move || parse_terms(&upstream_page.body)).await??;
+        terms.extend(parsed.into_iter().map(|(slug, name, count)| TaxonomyTerm {
+            url: format!("{}/{}/{}/", base, taxonomy.path(), slug),
+            id: slug,
+            name,
+            count,
+        }));
+
+        if page >= total_pages.unwrap_or(1).min(MAX_TERM_PAGES) {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(terms)
+}
+
+// Satu halaman katalog dari endpoint posts REST API, total diambil dari header X-WP-Total
+pub(crate) async fn fetch_rest_catalog(scraper: &FilmapikScraper, page: u32) -> Result<CatalogPage, AppError> {
+    let base = scraper.base_url();
+    let url = format!(
+        "{}/wp-json/wp/v2/posts?per_page={}&page={}&_fields=slug,title,modified_gmt",
+        base, REST_PER_PAGE, page
+    );
+    let upstream_page = scraper.fetch_page(&url, format!("{}/", base)).await?;
+    let total_items = header_u32(&upstream_page, "x-wp-total");
+    let total_pages = header_u32(&upstream_page, "x-wp-totalpages");
+
+    let posts = run_blocking(move || parse_posts(&upstream_page.body)).await??;
+    let entries = posts
+        .into_iter()
+        .map(|(slug, title, modified)| CatalogEntry {
+            url: format!("{}/{}/", base, slug),
+            id: slug,
+            title: Some(title),
+            modified,
+            provider: scraper.site().id.clone(),
+        })
+        .collect();
+
+    Ok(CatalogPage {
+        page,
+        per_page: REST_PER_PAGE,
+        total_items,
+        total_pages,
+        source: "rest".to_string(),
+        entries,
+    })
+}
+
+// Semua film dari sitemap post, dipakai jika REST API tidak tersedia
+pub(crate) async fn fetch_sitemap_catalog(scraper: &FilmapikScraper) -> Result<Vec<CatalogEntry>, AppError> {
+    let base = scraper.base_url();
+    let prefixes = ["wp-sitemap-posts-post-".to_string(), "post-sitemap".to_string()];
+    let entries = fetch_sitemap_urls(scraper, &prefixes).await?;
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            let slug = slug_from_url(&entry.loc)?;
+            Some(CatalogEntry {
+                url: format!("{}/{}/", base, slug),
+                id: slug,
+                title: None,
+                modified: entry.lastmod,
+                provider: scraper.site().id.clone(),
+            })
+        })
+        .collect())
+}
+
+// Mengambil isi semua sitemap yang nama filenya diawali salah satu prefix
+async fn fetch_sitemap_urls(scraper: &FilmapikScraper, prefixes: &[String]) -> Result<Vec<SitemapEntry>, AppError> {
+    let base = scraper.base_url();
+    let mut last_error = None;
+
+    for index in SITEMAP_INDEXES {
+        let index_url = format!("{}/{}", base, index);
+        let children = match scraper.fetch_page(&index_url, format!("{}/", base)).await {
+            Ok(page) => parse_sitemap(&page.body),
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            },
+        };
+
+        let files: Vec<String> = children
+            .into_iter()
+            .map(|child| child.loc)
+            .filter(|loc| {
+                let file = loc.rsplit('/').next().unwrap_or_default();
+                prefixes.iter().any(|prefix| file.starts_with(prefix.as_str()))
+            })
+            .take(MAX_SITEMAP_FILES)
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+
+        let mut entries = Vec::new();
+        for file in files {
+            let page = scraper.fetch_page(&file, index_url.clone()).await?;
+            entries.extend(parse_sitemap(&page.body));
+        }
+        info!("{} URL ditemukan di sitemap {}", entries.len(), index_url);
+        return Ok(entries);
+    }
+
+    Err(last_error.unwrap_or_else(|| AppError::UpstreamNotFound {
+        url: format!("{}/{}", base, SITEMAP_INDEXES[0]),
+    }))
+}
+
+fn header_u32(page: &UpstreamPage, name: &str) -> Option<u32> {
+    page.headers.get(name).and_then(|v| v.trim().parse().ok())
+}
+
+// Parse response REST API term menjadi (slug, nama, jumlah film)
+pub(crate) fn parse_terms(body: &str) -> Result<Vec<(String, String, Option<u32>)>, AppError> {
+    let terms: Vec<WpTerm> = serde_json::from_str(body)?;
+    Ok(terms
+        .into_iter()
+        .filter(|term| !term.slug.is_empty() && term.slug != "uncategorized")
+        .map(|term| (term.slug, decode_html(&term.name), term.count))
+        .collect())
+}
+
+// Parse response REST API posts menjadi (slug, judul, waktu perubahan)
+pub(crate) fn parse_posts(body: &str) -> Result<Vec<(String, String, Option<String>)>, AppError> {
+    let posts: Vec<WpPost> = serde_json::from_str(body)?;
+    Ok(posts
+        .into_iter()
+        .filter(|post| !post.slug.is_empty())
+        .map(|post| {
+            // modified_gmt tidak memiliki zona waktu, tambahkan "Z" agar menjadi RFC 3339
+            let modified = post.modified_gmt.filter(|m| !m.is_empty()).map(|m| format!("{}Z", m));
+            (post.slug, decode_html(&post.title.rendered), modified)
+        })
+        .collect())
+}
+
+// Parse sitemap XML (urlset maupun sitemap index)
+pub(crate) fn parse_sitemap(xml: &str) -> Vec<SitemapEntry> {
+    SITEMAP_ENTRY_RE
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let inner = caps.get(2)?.as_str();
+            let loc = xml_text(SITEMAP_LOC_RE.captures(inner)?.get(1)?.as_str());
+            if loc.is_empty() {
+                return None;
+            }
+            let lastmod = SITEMAP_LASTMOD_RE
+                .captures(inner)
+                .and_then(|c| c.get(1))
+                .map(|m| xml_text(m.as_str()))
+                .filter(|m| !m.is_empty());
+            Some(SitemapEntry { loc, lastmod })
+        })
+        .collect()
+}
+
+fn xml_text(raw: &str) -> String {
+    let raw = raw.trim();
+    let raw = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|r| r.strip_suffix("]]>"))
+        .unwrap_or(raw);
+    raw.replace("&amp;", "&").trim().to_string()
+}
+
+// Nama dan judul dari REST API berisi entity HTML (misalnya "Action &amp; Adventure")
+fn decode_html(text: &str) -> String {
+    scraper::Html::parse_fragment(text)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+// Slug adalah segmen terakhir path URL
+fn slug_from_url(url: &str) -> Option<String> {
+    let path = url::Url::parse(url).ok()?.path().to_string();
+    path.rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .map(str::to_string)
+}
+
+// Sitemap tidak memuat nama term, jadi nama dibentuk dari slug ("science-fiction" -> "Science Fiction")
+pub(crate) fn name_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rest_terms_and_posts() {
+        let terms = parse_terms(
+            r#"[{"slug":"action-adventure","name":"Action &amp; Adventure","count":152},
+                {"slug":"uncategorized","name":"Uncategorized","count":1},
+                {"slug":"usa","name":"USA"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            terms,
+            vec![
+                ("action-adventure".to_string(), "Action & Adventure".to_string(), Some(152)),
+                ("usa".to_string(), "USA".to_string(), None),
+            ]
+        );
+
+        let posts = parse_posts(
+            r#"[{"slug":"mr-mrs-smith-2005","title":{"rendered":"Mr. &#038; Mrs. Smith (2005)"},"modified_gmt":"2024-05-01T10:00:00"}]"#,
+        )
+        .unwrap();
+        assert_eq!(posts[0].1, "Mr. & Mrs. Smith (2005)");
+        assert_eq!(posts[0].2.as_deref(), Some("2024-05-01T10:00:00Z"));
+
+        assert!(parse_terms("<html>bukan json</html>").is_err());
+    }
+
+    #[test]
+    fn parses_sitemaps() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <url><loc>https://filmapik.example/the-matrix-1999/</loc><lastmod>2024-01-02T03:04:05+00:00</lastmod></url>
+              <url>
+                <loc><![CDATA[https://filmapik.example/?p=12&amp;x=1]]></loc>
+              </url>
+            </urlset>"#;
+        let entries = parse_sitemap(xml);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].loc, "https://filmapik.example/the-matrix-1999/");
+        assert_eq!(entries[0].lastmod.as_deref(), Some("2024-01-02T03:04:05+00:00"));
+        assert_eq!(entries[1].loc, "https://filmapik.example/?p=12&x=1");
+        assert_eq!(entries[1].lastmod, None);
+
+        assert_eq!(slug_from_url("https://filmapik.example/country/hong-kong/").as_deref(), Some("hong-kong"));
+        assert_eq!(name_from_slug("science-fiction"), "Science Fiction");
+        assert_eq!(name_from_slug("2023"), "2023");
+    }
+}
@@ -0,0 +1,962 @@
+// Test end-to-end seluruh endpoint terhadap mock upstream FilmApik yang menyajikan HTML rekaman di tests/fixtures/
+// Butuh endpoint HTTP, test ini dilewati jika dibangun tanpa feature "server"
+#![cfg(feature = "server")]
+use actix_web::{http::StatusCode, middleware, test, web, App};
+use filmapik_api::scraper::{FilmapikScraper, ProviderRegistry, Site};
+use filmapik_api::{api, config, request_id};
+use serde_json::Value;
+use std::sync::{Arc, Once};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LISTING_HTML: &str = include_str!("fixtures/listing.html");
+const EMPTY_LISTING_HTML: &str = include_str!("fixtures/empty_listing.html");
+const DETAIL_HTML: &str = include_str!("fixtures/detail.html");
+const NOT_FOUND_HTML: &str = include_str!("fixtures/not_found.html");
+const CHALLENGE_HTML: &str = include_str!("fixtures/challenge.html");
+
+const MOVIE_ID: &str = "paayum-oli-nee-yenakku-2023";
+const ORIGINAL_STREAM_URL: &str = "https://filmdewasa.org/embed/paayum-oli-nee-yenakku";
+
+static ENV: Once = Once::new();
+static UPSTREAM: OnceCell<MockServer> = OnceCell::const_new();
+
+fn html(status: u16, body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_raw(body.to_string(), "text/html; charset=UTF-8")
+}
+
+async fn mount(server: &MockServer, url_path: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(url_path))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+// Mock upstream dipakai bersama oleh semua test karena base URL FilmApik bersifat global
+async fn upstream() -> &'static MockServer {
+    ENV.call_once(|| {
+        // Test tidak perlu menulis snapshot HTML ke disk
+        std::env::set_var("SNAPSHOT_MAX_BYTES", "0");
+        // Batas kecil agar discover tanpa filter berhenti sebelum listing habis
+        std::env::set_var("DISCOVER_MAX_PAGES", "3");
+        // Cukup untuk memeriksa seluruh 30 halaman di test film acak, tetapi lebih kecil dari rentang tahun
+        std::env::set_var("RANDOM_MAX_PAGES", "40");
+        // Batas expand singkat agar detail yang lambat di test tidak menahan respons
+        std::env::set_var("EXPAND_BUDGET_MS", "500");
+    });
+
+    UPSTREAM
+        .get_or_init(|| async {
+            let server = MockServer::start().await;
+            let stream_url = format!("{}/embed/paayum-oli-nee-yenakku", server.uri());
+
+            for listing_path in ["/", "/page/2/", "/page/3/", "/best-rating/", "/best-rating/page/2/", "/genre/action/", "/country/india/", "/year/2023/", "/quality/bluray/"] {
+                mount(&server, listing_path, html(200, LISTING_HTML)).await;
+            }
+            mount(&server, "/cast/vikram-prabhu/", html(200, &LISTING_HTML.replace(">Film Terbaru</h1>", ">Cast: Vikram Prabhu</h1>"))).await;
+            mount(&server, "/genre/empty-genre/", html(200, EMPTY_LISTING_HTML)).await;
+            mount(&server, "/sister/", html(200, LISTING_HTML)).await;
+
+            mount(&server, &format!("/{}/", MOVIE_ID), html(200, &DETAIL_HTML.replace(ORIGINAL_STREAM_URL, &stream_url))).await;
+            mount(&server, "/no-stream-film/", html(200, &DETAIL_HTML.replace("<iframe", "<div"))).await;
+            mount(
+                &server,
+                "/embed/paayum-oli-nee-yenakku",
+                html(200, "<html><body>player</body></html>").insert_header("x-frame-options", "DENY"),
+            )
+            .await;
+
+            mount(&server, "/missing-film/", html(404, NOT_FOUND_HTML)).await;
+            mount(&server, "/soft-404-film/", html(200, NOT_FOUND_HTML)).await;
+            mount(&server, "/deleted-film/", html(410, NOT_FOUND_HTML)).await;
+            mount(&server, "/blocked-film/", html(503, CHALLENGE_HTML).insert_header("server", "cloudflare")).await;
+            mount(&server, "/country/limited/", html(429, "Too Many Requests").insert_header("retry-after", "120")).await;
+
+            config::set_filmapik_url(&server.uri());
+            server
+        })
+        .await
+}
+
+// FilmApik sebagai provider default ditambah situs "sister" di bawah /sister pada mock upstream
+fn providers(server: &MockServer, scraper: &FilmapikScraper) -> ProviderRegistry {
+    let mut registry = ProviderRegistry::new(Arc::new(scraper.clone()));
+    let sister = Site {
+        id: "sister".to_string(),
+        name: "Sister".to_string(),
+        base_url: Some(format!("{}/sister", server.uri())),
+    };
+    registry.register(Arc::new(scraper.with_site(sister))).unwrap();
+    registry
+}
+
+// Membuat aplikasi dengan scraper (dan cache) baru untuk setiap test
+macro_rules! init_app {
+    () => {{
+        let server = upstream().await;
+        let scraper = FilmapikScraper::new();
+        let providers = providers(server, &scraper);
+        test::init_service(
+            App::new()
+                .wrap(middleware::from_fn(request_id::middleware))
+                .app_data(web::Data::new(scraper))
+                .app_data(web::Data::new(providers))
+                .configure(api::configure),
+        )
+        .await
+    }};
+}
+
+// Aplikasi dengan provider "feed" di `{server}/feed` dan scraper baru agar halaman upstream tidak diambil dari cache
+macro_rules! feed_app {
+    ($feed:expr) => {{
+        let scraper = FilmapikScraper::new();
+        let mut registry = ProviderRegistry::new(Arc::new(scraper.clone()));
+        let site = Site {
+            id: "feed".to_string(),
+            name: "Feed".to_string(),
+            base_url: Some(format!("{}/feed", $feed.uri())),
+        };
+        registry.register(Arc::new(scraper.with_site(site))).unwrap();
+        test::init_service(App::new().app_data(web::Data::new(registry)).configure(api::configure)).await
+    }};
+}
+
+macro_rules! get_json {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::call_service(&$app, test::TestRequest::get().uri($uri).to_request()).await;
+        let status = resp.status();
+        let body: Value = test::read_body_json(resp).await;
+        (status, body)
+    }};
+}
+
+macro_rules! post_json {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::call_service(&$app, test::TestRequest::post().uri($uri).to_request()).await;
+        let status = resp.status();
+        let body: Value = test::read_body_json(resp).await;
+        (status, body)
+    }};
+}
+
+fn assert_movie_list(body: &Value, page: u64) {
+    assert_eq!(body["status"], "success");
+    assert_eq!(body["pagination"]["current_page"], page);
+    assert_eq!(body["pagination"]["per_page"], 24);
+
+    let movies = body["data"].as_array().unwrap();
+    assert_eq!(movies.len(), 24);
+    for movie in movies {
+        assert!(!movie["id"].as_str().unwrap().is_empty());
+        assert!(!movie["title"].as_str().unwrap().is_empty());
+        assert!(!movie["poster"].as_str().unwrap().is_empty());
+        assert!(movie["url"].as_str().unwrap().starts_with("http"));
+        assert!(movie["genres"].is_array());
+    }
+}
+
+fn assert_error(body: &Value, code: &str) {
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["code"], code);
+    assert!(!body["message"].as_str().unwrap().is_empty());
+    assert!(!body["request_id"].as_str().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn latest_movies() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/latest");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+    assert_eq!(body["data"][0]["id"], "paayum-oli-nee-yenakku-2022");
+    assert_eq!(body["data"][0]["year"], 2022);
+    assert_eq!(body["data"][0]["provider"], "filmapik");
+
+    let (status, body) = get_json!(app, "/api/movie/latest?page=2");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 2);
+}
+
+#[actix_web::test]
+async fn invalid_page_is_rejected() {
+    let app = init_app!();
+
+    for uri in ["/api/movie/latest?page=0", "/api/movie/latest?page=abc", "/api/movie/popular?page=-1"] {
+        let (status, body) = get_json!(app, uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_error(&body, "INVALID_PARAMETER");
+        assert_eq!(body["details"]["parameter"], "page");
+    }
+}
+
+#[actix_web::test]
+async fn popular_movies() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/popular");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    let (status, body) = get_json!(app, "/api/movie/popular?page=2");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 2);
+}
+
+#[actix_web::test]
+async fn genres() {
+    let app = init_app!();
+    let server = upstream().await;
+
+    let (status, body) = get_json!(app, "/api/movie/genre");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+    let genres = body["data"].as_array().unwrap();
+    assert!(!genres.is_empty());
+    assert_eq!(genres[0]["id"], "action");
+    assert_eq!(genres[0]["url"], format!("{}/genre/action/", server.uri()));
+}
+
+#[actix_web::test]
+async fn movies_by_genre() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/genre/action");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    // Genre tanpa film tetap valid selama halaman memiliki container daftar film
+    let (status, body) = get_json!(app, "/api/movie/genre/empty-genre");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 0);
+
+    let (status, body) = get_json!(app, "/api/movie/genre/action%2F..");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "genre_id");
+}
+
+#[actix_web::test]
+async fn countries() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/country");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+    let countries = body["data"].as_array().unwrap();
+    assert!(countries.iter().any(|c| c["id"] == "india"));
+}
+
+#[actix_web::test]
+async fn movies_by_country() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/country/india");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    let (status, body) = get_json!(app, "/api/movie/country/limited");
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_error(&body, "UPSTREAM_RATE_LIMITED");
+    assert_eq!(body["details"]["retry_after"], 120);
+    assert_eq!(body["upstream"]["status"], 429);
+}
+
+#[actix_web::test]
+async fn rate_limited_sets_retry_after() {
+    let app = init_app!();
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/movie/country/limited").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(resp.headers().get("retry-after").unwrap(), "120");
+    assert!(resp.headers().contains_key("x-request-id"));
+}
+
+#[actix_web::test]
+async fn search_movies() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/search?q=rumah%20dara");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    let (status, body) = get_json!(app, "/api/movie/search?q=%20");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_error(&body, "INVALID_PARAMETER");
+    assert_eq!(body["details"]["parameter"], "q");
+}
+
+#[actix_web::test]
+async fn listings_sort_across_upstream_pages() {
+    let app = init_app!();
+
+    // Halaman 1-3 terbaru sama persis sehingga jendela berisi 24 film unik setelah deduplikasi
+    let (status, body) = get_json!(app, "/api/movie/latest?sort=rating");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pagination"]["total_items"], 24);
+    assert_eq!(body["pagination"]["total_pages"], 1);
+    let ratings: Vec<f64> = body["data"].as_array().unwrap().iter().map(|m| m["rating"].as_f64().unwrap()).collect();
+    assert_eq!(ratings.len(), 24);
+    assert!(ratings.windows(2).all(|w| w[0] >= w[1]));
+    assert_eq!(ratings[0], 8.4);
+
+    let (status, body) = get_json!(app, "/api/movie/genre/action?sort=year&order=asc");
+    assert_eq!(status, StatusCode::OK);
+    let years: Vec<i64> = body["data"].as_array().unwrap().iter().map(|m| m["year"].as_i64().unwrap()).collect();
+    assert!(years.windows(2).all(|w| w[0] <= w[1]));
+
+    // Halaman di luar jendela yang diurutkan kosong
+    let (status, body) = get_json!(app, "/api/movie/country/india?sort=title&page=2");
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].as_array().unwrap().is_empty());
+
+    let (status, body) = get_json!(app, "/api/movie/popular?order=desc");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "order");
+}
+
+#[actix_web::test]
+async fn listings_support_client_page_size() {
+    let app = init_app!();
+
+    let (_, upstream_page) = get_json!(app, "/api/movie/latest");
+    let upstream_ids: Vec<&str> = upstream_page["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap()).collect();
+
+    let (status, body) = get_json!(app, "/api/movie/latest?per_page=10&page=2");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pagination"]["current_page"], 2);
+    assert_eq!(body["pagination"]["per_page"], 10);
+    assert_eq!(body["pagination"]["offset"], 10);
+    let ids: Vec<&str> = body["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, upstream_ids[10..20]);
+
+    // Halaman 2 dan 3 mock upstream berisi film yang sama dengan halaman 1 sehingga semuanya dibuang,
+    // halaman 4 tidak ada sehingga total diketahui
+    let (status, body) = get_json!(app, "/api/movie/latest?limit=30");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 24);
+    assert_eq!(body["pagination"]["total_items"], 24);
+    assert!(body.get("next_cursor").is_none());
+
+    // Halaman 2 genre tidak ada (404) sehingga total diketahui
+    let (status, body) = get_json!(app, "/api/movie/genre/action?limit=60");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 24);
+    assert_eq!(body["pagination"]["total_items"], 24);
+    assert_eq!(body["pagination"]["total_pages"], 1);
+
+    // Tanpa per_page/limit/offset pagination tidak berubah
+    assert!(upstream_page["pagination"].get("offset").is_none());
+
+    let (status, body) = get_json!(app, "/api/movie/year/2023?page=2&offset=10");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "offset");
+}
+
+// Listing sintetis dari artikel-artikel fixture, dipakai untuk mensimulasikan film baru yang menggeser halaman
+fn listing_with(articles: &[String]) -> String {
+    let start = LISTING_HTML.find("<article").unwrap();
+    let end = LISTING_HTML.rfind("</article>").unwrap() + "</article>".len();
+    format!("{}{}{}", &LISTING_HTML[..start], articles.concat(), &LISTING_HTML[end..])
+}
+
+fn fixture_articles() -> Vec<String> {
+    let start = LISTING_HTML.find("<article").unwrap();
+    let end = LISTING_HTML.rfind("</article>").unwrap() + "</article>".len();
+    LISTING_HTML[start..end]
+        .split_inclusive("</article>")
+        .map(|article| article.trim_start().to_string())
+        .collect()
+}
+
+// Mengganti navigasi pagination listing agar halaman terakhirnya last_page
+fn with_last_page(listing: &str, last_page: u32) -> String {
+    let start = listing.find("<div class=\"pagination\">").unwrap();
+    let end = start + listing[start..].find("</ul></div>").unwrap() + "</ul></div>".len();
+    let links: String = (2..=last_page)
+        .map(|page| format!("<li><a class=\"page-numbers\" href=\"/page/{0}/\">{0}</a></li>", page))
+        .collect();
+    format!(
+        "{}<div class=\"pagination\"><ul class=\"page-numbers\"><li><span class=\"page-numbers current\">1</span></li>{}</ul></div>{}",
+        &listing[..start],
+        links,
+        &listing[end..]
+    )
+}
+
+#[actix_web::test]
+async fn cursors_skip_movies_shifted_by_new_uploads() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    let articles = fixture_articles();
+    mount(&feed, "/feed/", html(200, &listing_with(&articles[..12]))).await;
+    mount(&feed, "/feed/page/2/", html(200, &listing_with(&articles[12..]))).await;
+
+    let ids = |body: &Value| -> Vec<String> {
+        body["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap().to_string()).collect()
+    };
+
+    let app = feed_app!(feed);
+    let (status, first) = get_json!(app, "/api/movie/latest?provider=feed&per_page=10");
+    assert_eq!(status, StatusCode::OK);
+    let first_ids = ids(&first);
+    assert_eq!(first_ids.len(), 10);
+    assert!(first.get("prev_cursor").is_none());
+    let next_cursor = first["next_cursor"].as_str().unwrap().to_string();
+
+    // Tiga film terakhir diunggah ulang ke awal listing sehingga film ke-10 dan seterusnya bergeser ke halaman 2
+    let shifted_first: Vec<String> = articles[21..].iter().chain(&articles[..9]).cloned().collect();
+    for (url_path, page) in [("/feed/", shifted_first), ("/feed/page/2/", articles[9..21].to_vec())] {
+        Mock::given(method("GET"))
+            .and(path(url_path))
+            .respond_with(html(200, &listing_with(&page)))
+            .with_priority(1)
+            .mount(&feed)
+            .await;
+    }
+
+    let app = feed_app!(feed);
+    let (status, second) = get_json!(app, &format!("/api/movie/latest?provider=feed&cursor={}", next_cursor));
+    assert_eq!(status, StatusCode::OK);
+    let second_ids = ids(&second);
+    assert_eq!(second_ids.len(), 10);
+    assert!(second_ids.iter().all(|id| !first_ids.contains(id)), "duplikat setelah listing bergeser");
+    let (_, reference) = get_json!(app, "/api/movie/latest?provider=feed&limit=10&offset=13");
+    assert_eq!(second_ids, ids(&reference));
+
+    // Cursor sebelumnya kembali ke film pertama yang sudah dilihat, tanpa film baru
+    let prev_cursor = second["prev_cursor"].as_str().unwrap();
+    let (status, back) = get_json!(app, &format!("/api/movie/latest?provider=feed&cursor={}", prev_cursor));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&back), first_ids);
+
+    // Cursor tidak bisa dipakai di listing lain, dipalsukan, atau digabung dengan page
+    for uri in [
+        format!("/api/movie/popular?provider=feed&cursor={}", next_cursor),
+        format!("/api/movie/latest?provider=feed&cursor={}x", next_cursor),
+        format!("/api/movie/latest?provider=feed&page=2&cursor={}", next_cursor),
+    ] {
+        let (status, body) = get_json!(app, &uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "uri {}", uri);
+        assert_eq!(body["details"]["parameter"], "cursor");
+    }
+}
+
+#[actix_web::test]
+async fn expand_detail_enriches_within_budget() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    let articles = fixture_articles();
+    mount(&feed, "/feed/", html(200, &listing_with(&articles[..3]))).await;
+
+    let app = feed_app!(feed);
+    let (_, plain) = get_json!(app, "/api/movie/latest?provider=feed");
+    let ids: Vec<String> = plain["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap().to_string()).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(plain["data"][0].get("expanded").is_none());
+
+    // Film pertama punya detail, film kedua terlalu lambat, film ketiga tidak ditemukan
+    mount(&feed, &format!("/feed/{}/", ids[0]), html(200, DETAIL_HTML)).await;
+    mount(&feed, &format!("/feed/{}/", ids[1]), html(200, DETAIL_HTML).set_delay(Duration::from_secs(3))).await;
+
+    let app = feed_app!(feed);
+    let started = std::time::Instant::now();
+    let (status, body) = get_json!(app, "/api/movie/latest?provider=feed&expand=detail");
+    assert_eq!(status, StatusCode::OK);
+    assert!(started.elapsed() < Duration::from_secs(2), "expand menunggu detail yang lambat");
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data[0]["expanded"], true);
+    assert_eq!(data[0]["director"], "Karthik Adwait");
+    assert!(data[0]["description"].is_string());
+    assert_eq!(data[1]["expanded"], false);
+    assert!(data[1].get("description").is_none());
+    assert_eq!(data[2]["expanded"], false);
+
+    // Film terkait di halaman detail dilengkapi dengan cara yang sama
+    mount(&feed, &format!("/feed/{}/", MOVIE_ID), html(200, DETAIL_HTML)).await;
+    mount(&feed, "/feed/rumah-dara-2023/", html(200, DETAIL_HTML)).await;
+    let (status, body) = get_json!(app, &format!("/api/movie/{}?provider=feed&expand=detail", MOVIE_ID));
+    assert_eq!(status, StatusCode::OK);
+    let related = body["data"]["related_movies"].as_array().unwrap();
+    // Film terkait pertama sama dengan film kedua di listing yang detailnya lambat
+    assert_eq!(related[0]["id"], ids[1]);
+    assert_eq!(related[0]["expanded"], false);
+    assert_eq!(related[1]["id"], "rumah-dara-2023");
+    assert_eq!(related[1]["expanded"], true);
+    assert_eq!(related[1]["year"], 2023);
+    assert_eq!(related[2]["expanded"], false);
+
+    let (status, body) = get_json!(app, "/api/movie/latest?provider=feed&expand=cast");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "expand");
+}
+
+#[actix_web::test]
+async fn random_movie_samples_discovered_pages() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    let articles = fixture_articles();
+    mount(&feed, "/feed/", html(200, &with_last_page(&listing_with(&articles[..12]), 2))).await;
+    mount(&feed, "/feed/page/2/", html(200, &with_last_page(&listing_with(&articles[12..]), 2))).await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/feed/[^/]+/$"))
+        .respond_with(html(200, DETAIL_HTML))
+        .mount(&feed)
+        .await;
+
+    let app = feed_app!(feed);
+    let (_, listing) = get_json!(app, "/api/movie/latest?provider=feed&per_page=24");
+    let ids: Vec<String> = listing["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap().to_string()).collect();
+    assert_eq!(ids.len(), 24);
+
+    // Kedua halaman dari navigasi ikut terpilih
+    let mut pages = std::collections::HashSet::new();
+    for seed in 0..16 {
+        let (status, body) = get_json!(app, &format!("/api/movie/random?provider=feed&seed={}", seed));
+        assert_eq!(status, StatusCode::OK, "seed {}", seed);
+        assert_eq!(body["data"]["director"], "Karthik Adwait");
+        let position = ids.iter().position(|id| body["data"]["id"] == id.as_str()).unwrap();
+        pages.insert(position / 12 + 1);
+    }
+    assert_eq!(pages.len(), 2);
+
+    // Seed yang sama memberikan film yang sama, juga dengan cache yang baru
+    let (_, first) = get_json!(app, "/api/movie/random?provider=feed&seed=42");
+    let app = feed_app!(feed);
+    let (_, second) = get_json!(app, "/api/movie/random?provider=feed&seed=42");
+    assert_eq!(first["data"]["id"], second["data"]["id"]);
+
+    let (status, body) = get_json!(app, "/api/movie/random?provider=feed&min_rating=10&seed=1");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "NOT_FOUND");
+
+    let (status, body) = get_json!(app, "/api/movie/random?provider=feed&year_from=2023&year_to=2020");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "year_to");
+}
+
+#[actix_web::test]
+async fn random_movie_finds_the_only_matching_page() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    let rating = regex::Regex::new(r#"icon_star"></span> [0-9.]+"#).unwrap();
+    let articles: Vec<String> = fixture_articles()
+        .iter()
+        .map(|article| rating.replace(article, r#"icon_star"></span> 5.0"#).into_owned())
+        .collect();
+    let mut rated = articles[..12].to_vec();
+    rated[3] = rating.replace(&rated[3], r#"icon_star"></span> 9.9"#).into_owned();
+
+    // 30 halaman dengan rating rendah, hanya halaman 17 yang berisi film dengan rating 9.9
+    let low = with_last_page(&listing_with(&articles[..12]), 30);
+    mount(&feed, "/feed/", html(200, &low)).await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/feed/page/[0-9]+/$"))
+        .respond_with(html(200, &low))
+        .mount(&feed)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/feed/page/17/"))
+        .respond_with(html(200, &with_last_page(&listing_with(&rated), 30)))
+        .with_priority(1)
+        .mount(&feed)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/feed/[^/]+/$"))
+        .respond_with(html(200, DETAIL_HTML))
+        .mount(&feed)
+        .await;
+
+    let app = feed_app!(feed);
+    let (_, listing) = get_json!(app, "/api/movie/latest?provider=feed&page=17");
+    let expected = listing["data"][3]["id"].clone();
+    assert_eq!(listing["data"][3]["rating"], 9.9);
+
+    for seed in 0..4 {
+        let app = feed_app!(feed);
+        let (status, body) = get_json!(app, &format!("/api/movie/random?provider=feed&min_rating=9.5&seed={}", seed));
+        assert_eq!(status, StatusCode::OK, "seed {}", seed);
+        assert_eq!(body["data"]["id"], expected);
+    }
+
+    // 404 setelah semua halaman dalam batas diperiksa
+    let (status, _) = get_json!(app, "/api/movie/random?provider=feed&min_rating=10&seed=1");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn random_movie_stays_within_page_budget() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    let low = with_last_page(&listing_with(&fixture_articles()[..12]), 30);
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/feed/year/[0-9]+/(page/[0-9]+/)?$"))
+        .respond_with(html(200, &low))
+        .mount(&feed)
+        .await;
+
+    // 21 listing tahun x 30 halaman tanpa film yang cocok, batas 40 halaman berlaku untuk semua tahun
+    let app = feed_app!(feed);
+    let (status, body) = get_json!(app, "/api/movie/random?provider=feed&year_from=2000&year_to=2020&min_rating=10&seed=3");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "NOT_FOUND");
+
+    let received = feed.received_requests().await.unwrap();
+    assert!(received.len() <= 40, "{} request upstream", received.len());
+    assert!(received.len() >= 30, "{} request upstream", received.len());
+}
+
+#[actix_web::test]
+async fn movie_batch_reports_failures_per_id() {
+    let app = init_app!();
+
+    let request = test::TestRequest::post()
+        .uri("/api/movie/batch")
+        .set_json(serde_json::json!({ "ids": [MOVIE_ID, "missing-film", "a/b", MOVIE_ID] }))
+        .to_request();
+    let resp = test::call_service(&app, request).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(resp).await;
+
+    assert_eq!(body["status"], "partial");
+    assert_eq!(body["succeeded"], 1);
+    assert_eq!(body["failed"], 2);
+    let items = body["data"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["id"], MOVIE_ID);
+    assert_eq!(items[0]["data"]["title"], "Paayum Oli Nee Yenakku (2023)");
+    assert!(items[0]["data"].get("parse_warnings").is_none());
+    assert_eq!(items[1]["error"]["code"], "NOT_FOUND");
+    assert_eq!(items[2]["error"]["code"], "INVALID_PARAMETER");
+
+    let too_many: Vec<String> = (0..51).map(|i| format!("film-{}", i)).collect();
+    for payload in [serde_json::json!({ "ids": [] }), serde_json::json!({ "ids": too_many }), serde_json::json!({ "id": MOVIE_ID })] {
+        let request = test::TestRequest::post().uri("/api/movie/batch").set_json(payload).to_request();
+        let resp = test::call_service(&app, request).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"]["parameter"], "ids");
+    }
+}
+
+#[actix_web::test]
+async fn movies_by_year_and_quality() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/year/2023");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    // Slug kualitas tidak peka huruf besar/kecil
+    let (status, body) = get_json!(app, "/api/movie/quality/BluRay");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+
+    let (status, body) = get_json!(app, "/api/movie/year/20x3");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "year");
+
+    // Tanpa WordPress, daftar tahun dibentuk dari rentang tahun dan daftar kualitas dari daftar statis
+    let (status, body) = get_json!(app, "/api/movie/year");
+    assert_eq!(status, StatusCode::OK);
+    let years = body["data"].as_array().unwrap();
+    assert_eq!(years.last().unwrap()["id"], "1950");
+    assert!(years[0]["url"].as_str().unwrap().ends_with(&format!("/year/{}/", years[0]["id"].as_str().unwrap())));
+
+    let (status, body) = get_json!(app, "/api/movie/quality");
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = body["data"].as_array().unwrap().iter().map(|q| q["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["HD", "CAM", "WEBDL", "BluRay"]);
+}
+
+#[actix_web::test]
+async fn person_movies_from_cast_and_director_pages() {
+    let app = init_app!();
+
+    // Link pemeran di halaman detail mengarah ke endpoint person
+    let (_, body) = get_json!(app, &format!("/api/movie/{}", MOVIE_ID));
+    assert_eq!(body["data"]["directors"][0]["id"], "karthik-adwait");
+    let actor_id = body["data"]["cast"][0]["id"].as_str().unwrap().to_string();
+    assert_eq!(actor_id, "vikram-prabhu");
+
+    let (status, body) = get_json!(app, &format!("/api/person/{}", actor_id));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["person"]["name"], "Vikram Prabhu");
+    assert_eq!(body["data"]["roles"], serde_json::json!(["cast"]));
+    assert!(!body["data"]["movies"].as_array().unwrap().is_empty());
+
+    // Tidak ada halaman pemeran maupun sutradara
+    let (status, body) = get_json!(app, "/api/person/nobody-at-all");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_error(&body, "NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn person_name_from_archive_heading() {
+    let feed = MockServer::start().await;
+    let app = feed_app!(feed);
+
+    // Nama dengan tanda hubung tidak bisa dibentuk ulang dari slug
+    let heading = LISTING_HTML.replace(">Film Terbaru</h1>", ">Lee Jung-jae</h1>");
+    mount(&feed, "/feed/cast/lee-jung-jae/", html(200, &heading)).await;
+    let (status, body) = get_json!(app, "/api/person/lee-jung-jae?provider=feed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["person"]["name"], "Lee Jung-jae");
+
+    // Halaman sutradara tanpa judul, nama dibentuk dari slug
+    let start = LISTING_HTML.find("<h1 class=\"page-title\"").unwrap();
+    let end = start + LISTING_HTML[start..].find("</h1>").unwrap() + "</h1>".len();
+    let no_heading = format!("{}{}", &LISTING_HTML[..start], &LISTING_HTML[end..]);
+    mount(&feed, "/feed/director/bong-joon-ho/", html(200, &no_heading)).await;
+    let (status, body) = get_json!(app, "/api/person/bong-joon-ho?provider=feed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["person"]["name"], "Bong Joon Ho");
+    assert_eq!(body["data"]["roles"], serde_json::json!(["director"]));
+}
+
+#[actix_web::test]
+async fn discover_combines_facets() {
+    let app = init_app!();
+
+    // Mock upstream mengabaikan parameter pencarian lanjutan, jadi discover memfilter halaman /year/2023/
+    let (status, body) = get_json!(app, "/api/movie/discover?year=2023&quality=WEBDL");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["strategy"], "listing");
+    assert_eq!(body["partial"], false);
+    assert_eq!(body["pagination"]["total_items"], 5);
+    assert!(body["data"].as_array().unwrap().iter().all(|m| m["quality"] == "WEBDL"));
+
+    // Negara selalu menjadi sumber listing, genre dan rating difilter dari item lalu diurutkan
+    let (status, body) = get_json!(app, "/api/movie/discover?country=india&genre=science-fiction&min_rating=7&sort=rating");
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = body["data"].as_array().unwrap().iter().map(|m| m["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["mission-impossible-–-dead-reckoning-part-one-2023", "concrete-utopia-2023"]);
+
+    // Tanpa facet, listing terbaru dipindai sampai batas DISCOVER_MAX_PAGES
+    let (status, body) = get_json!(app, "/api/movie/discover?min_rating=9");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["partial"], true);
+    assert!(body["note"].as_str().unwrap().contains("3 halaman"));
+    assert!(body["pagination"]["total_items"].is_null());
+
+    let (status, body) = get_json!(app, "/api/movie/discover?sort=random");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["details"]["parameter"], "sort");
+}
+
+#[actix_web::test]
+async fn catalog_without_wordpress_uses_latest_listing() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/catalog");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["source"], "html");
+    assert!(body["pagination"]["total_items"].is_null());
+    assert!(!body["data"].as_array().unwrap().is_empty());
+    assert_eq!(body["data"][0]["provider"], "filmapik");
+}
+
+#[actix_web::test]
+async fn providers_are_routed_by_query_parameter() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/providers");
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = body["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["filmapik", "sister"]);
+    assert_eq!(body["data"][0]["default"], true);
+
+    // Situs lain diambil dari base URL-nya sendiri dan filmnya ditandai dengan ID provider
+    let (status, body) = get_json!(app, "/api/movie/latest?provider=sister");
+    assert_eq!(status, StatusCode::OK);
+    assert_movie_list(&body, 1);
+    assert!(body["data"].as_array().unwrap().iter().all(|m| m["provider"] == "sister"));
+
+    // Cache dibedakan per provider
+    let (_, body) = get_json!(app, "/api/movie/latest");
+    assert_eq!(body["data"][0]["provider"], "filmapik");
+
+    let (status, body) = get_json!(app, "/api/movie/latest?provider=unknown");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_error(&body, "INVALID_PARAMETER");
+    assert_eq!(body["details"]["parameter"], "provider");
+}
+
+// ID film dan orang adalah slug upstream yang hanya unik di dalam satu provider. Provider tidak
+// tersimpan di ID, sehingga ID dari listing provider lain harus dipakai bersama `?provider=` yang sama.
+#[actix_web::test]
+async fn ids_are_scoped_by_provider_query() {
+    let _ = upstream().await;
+    let feed = MockServer::start().await;
+    mount(&feed, "/feed/", html(200, LISTING_HTML)).await;
+    let feed_detail = DETAIL_HTML.replace(">Paayum Oli Nee Yenakku (2023)</h1>", ">Versi Feed (2023)</h1>");
+    mount(&feed, &format!("/feed/{}/", MOVIE_ID), html(200, &feed_detail)).await;
+    let feed_person = LISTING_HTML.replace(">Film Terbaru</h1>", ">Cast: Pemeran Feed</h1>");
+    mount(&feed, "/feed/cast/vikram-prabhu/", html(200, &feed_person)).await;
+    let app = feed_app!(feed);
+
+    // Situs dengan tema yang sama menghasilkan slug yang sama, hanya field provider yang berbeda
+    let (_, default_listing) = get_json!(app, "/api/movie/latest");
+    let (_, feed_listing) = get_json!(app, "/api/movie/latest?provider=feed");
+    assert_eq!(feed_listing["data"][0]["id"], default_listing["data"][0]["id"]);
+    assert_eq!(default_listing["data"][0]["provider"], "filmapik");
+    assert_eq!(feed_listing["data"][0]["provider"], "feed");
+
+    // Slug yang sama diarahkan ke provider dari `?provider=`, tanpa parameter ke provider default
+    let (status, body) = get_json!(app, &format!("/api/movie/{}?provider=feed", MOVIE_ID));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["title"], "Versi Feed (2023)");
+    assert_eq!(body["data"]["provider"], "feed");
+    let (_, body) = get_json!(app, &format!("/api/movie/{}", MOVIE_ID));
+    assert_eq!(body["data"]["title"], "Paayum Oli Nee Yenakku (2023)");
+    assert_eq!(body["data"]["provider"], "filmapik");
+
+    // Awalan provider di ID tidak didukung, ID seperti itu ditolak validasi slug
+    let (status, body) = get_json!(app, &format!("/api/movie/feed:{}", MOVIE_ID));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "INVALID_PARAMETER");
+
+    let (_, body) = get_json!(app, "/api/person/vikram-prabhu?provider=feed");
+    assert_eq!(body["data"]["person"]["name"], "Pemeran Feed");
+    let (_, body) = get_json!(app, "/api/person/vikram-prabhu");
+    assert_eq!(body["data"]["person"]["name"], "Vikram Prabhu");
+}
+
+#[actix_web::test]
+async fn movie_detail() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, &format!("/api/movie/{}", MOVIE_ID));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+
+    let detail = &body["data"];
+    assert_eq!(detail["id"], MOVIE_ID);
+    assert_eq!(detail["title"], "Paayum Oli Nee Yenakku (2023)");
+    assert_eq!(detail["year"], 2023);
+    assert_eq!(detail["rating"], 6.8);
+    assert_eq!(detail["quality"], "HD");
+    assert_eq!(detail["director"], "Karthik Adwait");
+    assert_eq!(detail["genres"], serde_json::json!(["Action", "Thriller"]));
+    assert_eq!(detail["actors"].as_array().unwrap().len(), 3);
+    assert_eq!(detail["related_movies"].as_array().unwrap().len(), 8);
+    assert_eq!(detail["completeness"], 1.0);
+    assert_eq!(detail["provider"], "filmapik");
+    assert_eq!(detail["related_movies"][0]["provider"], "filmapik");
+    assert!(detail.get("parse_warnings").is_none());
+}
+
+#[actix_web::test]
+async fn movie_detail_debug_warnings() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/no-stream-film?debug=true");
+    assert_eq!(status, StatusCode::OK);
+    let warnings = body["data"]["parse_warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["field"], "watch_url");
+    assert!(body["data"]["completeness"].as_f64().unwrap() < 1.0);
+
+    // Nilai flag yang salah ditolak dengan body error yang sama seperti validasi lain
+    for (uri, parameter) in [
+        ("/api/movie/no-stream-film?debug=maybe", "debug"),
+        ("/api/movie/no-stream-film?debug=true&debug=false", "debug"),
+        ("/api/movie/no-stream-film?expand=detail&expand=detail", "expand"),
+    ] {
+        let (status, body) = get_json!(app, uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "uri {}", uri);
+        assert_error(&body, "INVALID_PARAMETER");
+        assert_eq!(body["details"]["parameter"], parameter, "uri {}", uri);
+    }
+}
+
+#[actix_web::test]
+async fn upstream_errors_are_mapped() {
+    let app = init_app!();
+
+    let (status, body) = get_json!(app, "/api/movie/missing-film");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_error(&body, "NOT_FOUND");
+    assert_eq!(body["upstream"]["status"], 404);
+
+    let (status, body) = get_json!(app, "/api/movie/soft-404-film");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_error(&body, "NOT_FOUND");
+
+    let (status, body) = get_json!(app, "/api/movie/deleted-film");
+    assert_eq!(status, StatusCode::GONE);
+    assert_error(&body, "GONE");
+
+    let (status, body) = get_json!(app, "/api/movie/blocked-film");
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_error(&body, "UPSTREAM_BLOCKED");
+
+    // Slug dengan karakter yang tidak diizinkan tidak diteruskan ke upstream
+    let (status, body) = get_json!(app, "/api/movie/bad.id");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_error(&body, "INVALID_PARAMETER");
+    assert_eq!(body["details"]["parameter"], "movie_id");
+}
+
+#[actix_web::test]
+async fn watch_page() {
+    let app = init_app!();
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri(&format!("/api/movie/{}/watch", MOVIE_ID)).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("content-type").unwrap().to_str().unwrap().starts_with("text/html"));
+    let body = test::read_body(resp).await;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains(&format!("/api/movie/{}/watch/proxy", MOVIE_ID)));
+
+    let (status, body) = get_json!(app, "/api/movie/no-stream-film/watch");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_error(&body, "NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn watch_proxy() {
+    let app = init_app!();
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri(&format!("/api/movie/{}/watch/proxy", MOVIE_ID)).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("x-frame-options").is_none());
+    assert_eq!(resp.headers().get("content-security-policy").unwrap(), "frame-ancestors *");
+    let body = test::read_body(resp).await;
+    assert_eq!(&body[..], b"<html><body>player</body></html>");
+}
+
+#[actix_web::test]
+async fn cache_endpoints() {
+    let app = init_app!();
+
+    let (status, _) = get_json!(app, "/api/movie/latest");
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = get_json!(app, "/api/cache/stats");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+    for field in ["movies", "genres", "countries", "details", "not_found"] {
+        assert!(body["data"][field].is_u64(), "{}", field);
+    }
+
+    let (status, body) = post_json!(app, "/api/cache/clear");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+
+    let (status, body) = post_json!(app, "/api/cache/refresh/genres");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+
+    let (status, body) = post_json!(app, "/api/cache/refresh/countries");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "success");
+
+    let (status, body) = post_json!(app, "/api/cache/refresh/bogus");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_error(&body, "INVALID_PARAMETER");
+    assert_eq!(body["details"]["parameter"], "cache_type");
+}
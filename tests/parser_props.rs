@@ -0,0 +1,372 @@
+// Property test parser HTML: halaman listing dan detail dibangkitkan secara acak (node hilang,
+// whitespace berlebih, judul unicode, kurung bersarang) lalu hasil parse dicek terhadap invariant
+use filmapik_api::scraper::parser::{self, year_from_title};
+use filmapik_api::scraper::selectors::Selectors;
+use proptest::prelude::*;
+
+// Jumlah field detail yang dihitung pada skor kelengkapan (lihat parser.rs)
+const DETAIL_EXPECTED_FIELDS: f32 = 15.0;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn padding() -> impl Strategy<Value = String> {
+    prop::sample::select(vec!["", " ", "  ", "\n", "\t", " \n  "]).prop_map(str::to_string)
+}
+
+// Judul tanpa tag, termasuk karakter unicode, simbol HTML, dan kurung bersarang
+fn title_text() -> impl Strategy<Value = String> {
+    let word = prop_oneof![
+        "[a-zA-Z0-9]{1,10}",
+        "[\\p{L}\\p{N}]{1,6}",
+        Just("&".to_string()),
+        Just("<b>".to_string()),
+        Just("\"Kutip\"".to_string()),
+        Just("(Director's Cut)".to_string()),
+        Just("((Extended))".to_string()),
+        Just("(".to_string()),
+        Just(")".to_string()),
+        Just("Spider-Man:".to_string()),
+        Just("🎬".to_string()),
+    ];
+    prop::collection::vec(word, 1..6).prop_map(|words| words.join(" "))
+}
+
+// Judul lengkap beserta tahun yang seharusnya terbaca dari judul tersebut
+fn titled() -> impl Strategy<Value = (String, Option<i32>)> {
+    (title_text(), prop::option::of(1900i32..2100)).prop_map(|(title, year)| match year {
+        Some(year) => (format!("{} ({})", title, year), Some(year)),
+        None => {
+            let expected = year_from_title(&title);
+            (title, expected)
+        }
+    })
+}
+
+fn slug() -> impl Strategy<Value = String> {
+    "[a-z0-9]{1,8}(-[a-z0-9]{1,8}){0,4}"
+}
+
+#[derive(Debug, Clone)]
+struct ListingItem {
+    title: Option<(String, Option<i32>)>,
+    slug: String,
+    poster: Option<String>,
+    rating: Option<f32>,
+    quality: Option<String>,
+    genres: Vec<String>,
+    pad: String,
+}
+
+fn listing_item() -> impl Strategy<Value = ListingItem> {
+    (
+        prop::option::weighted(0.9, titled()),
+        slug(),
+        prop::option::of(slug()),
+        prop::option::of((0u32..=100).prop_map(|r| r as f32 / 10.0)),
+        prop::option::of(prop::sample::select(vec!["HD", "CAM", "Bluray", "WEB-DL"]).prop_map(str::to_string)),
+        prop::collection::vec("[A-Z][a-z]{2,8}", 0..4),
+        padding(),
+    )
+        .prop_map(|(title, slug, poster, rating, quality, genres, pad)| ListingItem {
+            title,
+            slug,
+            poster,
+            rating,
+            quality,
+            genres,
+            pad,
+        })
+}
+
+fn render_listing_item(item: &ListingItem) -> String {
+    let url = format!("http://localhost/{}/", item.slug);
+    let pad = &item.pad;
+    let mut html = String::from("<article class=\"item-infinite item\"><div class=\"content-thumbnail\">");
+    if let Some(poster) = &item.poster {
+        html.push_str(&format!("<a href=\"{}\"><img src=\"http://localhost/img/{}.jpg\"></a>", url, poster));
+    }
+    if let Some(rating) = item.rating {
+        html.push_str(&format!("<div class=\"gmr-rating-item\"><span class=\"icon_star\"></span>{}{}{}</div>", pad, rating, pad));
+    }
+    if let Some(quality) = &item.quality {
+        html.push_str(&format!("<div class=\"gmr-quality-item\"><a href=\"#\">{}{}{}</a></div>", pad, quality, pad));
+    }
+    html.push_str("</div><div class=\"item-article\">");
+    if let Some((title, _)) = &item.title {
+        html.push_str(&format!(
+            "<h2 class=\"entry-title\"><a href=\"{}\" title=\"{}\">{}{}{}</a></h2>",
+            url,
+            escape(title),
+            pad,
+            escape(title),
+            pad
+        ));
+    }
+    let genres: Vec<String> = item.genres.iter().map(|g| format!("<a href=\"#\">{}{}{}</a>", pad, g, pad)).collect();
+    html.push_str(&format!("<div class=\"gmr-movie-on\">{}</div>", genres.join(", ")));
+    html.push_str("</div></article>\n");
+    html
+}
+
+#[derive(Debug, Clone)]
+struct Detail {
+    title: Option<String>,
+    poster: bool,
+    description: Option<String>,
+    rating: Option<f32>,
+    iframe: bool,
+    genres: Vec<String>,
+    actors: Vec<String>,
+    // (nama author, jumlah dilihat, pakai pemisah titik)
+    views: Option<(String, u32, bool)>,
+    quality: Option<String>,
+    year: Option<i32>,
+    duration: Option<String>,
+    country: Option<String>,
+    release_date: Option<String>,
+    language: Option<String>,
+    director: Option<String>,
+    english_labels: bool,
+    pad: String,
+}
+
+fn meta_value() -> impl Strategy<Value = String> {
+    prop_oneof!["[a-zA-Z0-9 ]{1,12}", "[\\p{L}]{1,8}", Just("1:58:00".to_string()), Just("Tahun: 2020".to_string())]
+        .prop_map(|v| v.trim().to_string())
+        .prop_filter("nilai kosong", |v| !v.is_empty())
+}
+
+fn detail() -> impl Strategy<Value = Detail> {
+    let core = (
+        prop::option::weighted(0.9, title_text()),
+        any::<bool>(),
+        prop::option::of(title_text()),
+        prop::option::of((0u32..=100).prop_map(|r| r as f32 / 10.0)),
+        any::<bool>(),
+        prop::collection::vec("[A-Z][a-z]{2,8}", 0..4),
+        prop::collection::vec("[A-Z][a-z]{2,8}( [A-Z][a-z]{2,8})?", 0..4),
+        padding(),
+    );
+    let meta = (
+        prop::option::of((
+            prop::sample::select(vec!["admin", "dilihat", "adminDilihat", "dilihat 99"]).prop_map(str::to_string),
+            any::<u32>(),
+            any::<bool>(),
+        )),
+        prop::option::of(meta_value()),
+        prop::option::of(1900i32..2100),
+        prop::option::of(meta_value()),
+        prop::option::of(meta_value()),
+        prop::option::of(meta_value()),
+        prop::option::of(meta_value()),
+        prop::option::of(meta_value()),
+        any::<bool>(),
+    );
+    (core, meta).prop_map(
+        |(
+            (title, poster, description, rating, iframe, genres, actors, pad),
+            (views, quality, year, duration, country, release_date, language, director, english_labels),
+        )| Detail {
+            title,
+            poster,
+            description,
+            rating,
+            iframe,
+            genres,
+            actors,
+            views,
+            quality,
+            year,
+            duration,
+            country,
+            release_date,
+            language,
+            director,
+            english_labels,
+            pad,
+        },
+    )
+}
+
+// Angka dengan pemisah ribuan, misalnya 12345 -> "12,345" atau "12.345"
+fn thousands(value: u32, separator: char) -> String {
+    let digits = value.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn render_detail(detail: &Detail) -> String {
+    let pad = &detail.pad;
+    let label = |id: &str, en: &str| if detail.english_labels { en.to_string() } else { id.to_string() };
+    let mut html = String::from("<html><body><article><div class=\"gmr-movie-data\">");
+    if detail.poster {
+        html.push_str("<figure><img src=\"http://localhost/poster.jpg\"></figure>");
+    }
+    if let Some(title) = &detail.title {
+        html.push_str(&format!("<h1 class=\"entry-title\">{}{}{}</h1>", pad, escape(title), pad));
+    }
+    let genres: Vec<String> = detail.genres.iter().map(|g| format!("<a href=\"#\">{}</a>", g)).collect();
+    html.push_str(&format!("<div class=\"gmr-movie-on\">{}</div>", genres.join(", ")));
+    if let Some(rating) = detail.rating {
+        html.push_str(&format!(
+            "<div class=\"gmr-meta-rating\"><span itemprop=\"ratingValue\">{}{}{}</span></div>",
+            pad, rating, pad
+        ));
+    }
+    html.push_str("</div>");
+    if detail.iframe {
+        html.push_str("<iframe src=\"https://localhost/embed/film\"></iframe>");
+    }
+    html.push_str("<div class=\"gmr-movie-content\">");
+    if let Some(description) = &detail.description {
+        html.push_str(&format!("<p>{}{}{}</p>", pad, escape(description), pad));
+    }
+    html.push_str("</div>");
+    if let Some((author, views, dot)) = &detail.views {
+        html.push_str(&format!(
+            "<div class=\"gmr-moviedata\"><strong>Diposting pada:</strong> <time>30 Jun 2023</time> oleh {} dilihat {}</div>",
+            author,
+            thousands(*views, if *dot { '.' } else { ',' })
+        ));
+    }
+    let meta_rows = [
+        (label("Kualitas:", "Quality:"), &detail.quality),
+        (label("Tahun:", "Year:"), &detail.year.map(|y| y.to_string())),
+        (label("Durasi:", "Duration:"), &detail.duration),
+        (label("Negara:", "Country:"), &detail.country),
+        (label("Rilis:", "Release:"), &detail.release_date),
+        (label("Bahasa:", "Language:"), &detail.language),
+        (label("Direksi:", "Director:"), &detail.director),
+    ];
+    for (label, value) in meta_rows {
+        if let Some(value) = value {
+            html.push_str(&format!(
+                "<div class=\"gmr-moviedata\">{}<strong>{}</strong> <span>{}{}</span>{}</div>",
+                pad,
+                label,
+                escape(value),
+                pad,
+                pad
+            ));
+        }
+    }
+    html.push_str("<div class=\"gmr-castcrew\"><ul>");
+    for actor in &detail.actors {
+        html.push_str(&format!("<li><a href=\"#\">{}{}{}</a></li>", pad, actor, pad));
+    }
+    html.push_str("</ul></div></article></body></html>");
+    html
+}
+
+fn expected_warning_fields(detail: &Detail) -> Vec<&'static str> {
+    let title = detail.title.as_deref().is_some_and(|t| !t.trim().is_empty());
+    let description = detail.description.as_deref().is_some_and(|d| !d.trim().is_empty());
+    [
+        ("title", title),
+        ("poster", detail.poster),
+        ("description", description),
+        ("rating", detail.rating.is_some()),
+        ("watch_url", detail.iframe),
+        ("genres", !detail.genres.is_empty()),
+        ("actors", !detail.actors.is_empty()),
+        ("views", detail.views.is_some()),
+        ("quality", detail.quality.is_some()),
+        ("year", detail.year.is_some()),
+        ("duration", detail.duration.is_some()),
+        ("country", detail.country.is_some()),
+        ("release_date", detail.release_date.is_some()),
+        ("language", detail.language.is_some()),
+        ("director", detail.director.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, found)| !found)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn year_comes_from_last_parenthesized_group(title in title_text(), year in 1900i32..2100, pad in padding()) {
+        prop_assert_eq!(year_from_title(&format!("{} ({}){}", title, year, pad)), Some(year));
+        prop_assert_eq!(year_from_title(&format!("{} ({}) {}", title, year, "Extended")), None);
+    }
+
+    #[test]
+    fn listing_items_round_trip(items in prop::collection::vec(listing_item(), 0..12)) {
+        let selectors = Selectors::load_default().unwrap();
+        let html = format!(
+            "<html><body><div id=\"gmr-main-load\">{}</div></body></html>",
+            items.iter().map(render_listing_item).collect::<String>()
+        );
+        let listing = parser::parse_movie_list_with(&selectors, &html);
+        prop_assert!(listing.has_container);
+
+        // Item tanpa anchor judul dilewati, sisanya dipertahankan sesuai urutan
+        let expected: Vec<&ListingItem> = items.iter().filter(|item| item.title.is_some()).collect();
+        prop_assert_eq!(listing.movies.len(), expected.len());
+
+        for (movie, item) in listing.movies.iter().zip(expected) {
+            let (title, year) = item.title.as_ref().unwrap();
+            prop_assert_eq!(&movie.id, &item.slug);
+            prop_assert_eq!(&movie.title, title.trim());
+            prop_assert_eq!(movie.year, *year);
+            prop_assert_eq!(movie.rating, item.rating);
+            prop_assert_eq!(movie.quality.as_deref(), item.quality.as_deref());
+            prop_assert_eq!(&movie.genres, &item.genres);
+            prop_assert_eq!(movie.poster.is_empty(), item.poster.is_none());
+        }
+    }
+
+    #[test]
+    fn detail_fields_and_warnings_are_consistent(detail in detail()) {
+        let selectors = Selectors::load_default().unwrap();
+        let html = render_detail(&detail);
+        let parsed = parser::parse_movie_detail_with(&selectors, &html, "film", "http://localhost/film/");
+
+        let warnings: Vec<String> = parsed.parse_warnings.clone().unwrap().into_iter().map(|w| w.field).collect();
+        prop_assert_eq!(&warnings, &expected_warning_fields(&detail));
+        prop_assert!((0.0..=1.0).contains(&parsed.completeness));
+        prop_assert_eq!(parsed.completeness, 1.0 - warnings.len() as f32 / DETAIL_EXPECTED_FIELDS);
+
+        match detail.title.as_deref().map(str::trim) {
+            Some(title) => prop_assert_eq!(parsed.title.as_str(), title),
+            None => prop_assert_eq!(parsed.title.as_str(), "Unknown Title"),
+        }
+        prop_assert_eq!(parsed.rating, detail.rating);
+        prop_assert_eq!(parsed.year, detail.year);
+        prop_assert_eq!(parsed.views, detail.views.as_ref().map(|(_, views, _)| *views));
+        prop_assert_eq!(parsed.quality.as_deref(), detail.quality.as_deref());
+        prop_assert_eq!(parsed.duration.as_deref(), detail.duration.as_deref());
+        prop_assert_eq!(parsed.director.as_deref(), detail.director.as_deref());
+        prop_assert_eq!(&parsed.actors, &detail.actors);
+        prop_assert_eq!(&parsed.genres, &detail.genres);
+    }
+
+    #[test]
+    fn arbitrary_html_never_panics(html in "(<[a-z]{1,3}( class=\"[a-z -]{0,20}\")?>|</[a-z]{1,3}>|[^<>]{0,20}|Tahun:|dilihat|\\(|\\)){0,40}") {
+        let selectors = Selectors::load_default().unwrap();
+        let listing = parser::parse_movie_list_with(&selectors, &html);
+        prop_assert!(listing.movies.iter().all(|movie| !movie.id.contains('/')));
+
+        let parsed = parser::parse_movie_detail_with(&selectors, &html, "film", "http://localhost/film/");
+        let warnings = parsed.parse_warnings.unwrap();
+        let mut fields: Vec<_> = warnings.iter().map(|w| w.field.as_str()).collect();
+        fields.sort_unstable();
+        fields.dedup();
+        prop_assert_eq!(fields.len(), warnings.len());
+        prop_assert!((0.0..=1.0).contains(&parsed.completeness));
+    }
+}
@@ -0,0 +1,100 @@
+// Test mode record/replay: response yang direkam dari mock upstream harus bisa diputar ulang tanpa jaringan
+// Butuh endpoint HTTP, test ini dilewati jika dibangun tanpa feature "server"
+#![cfg(feature = "server")]
+use actix_web::{http::StatusCode, middleware, test, web, App};
+use filmapik_api::scraper::vcr::{self, Mode};
+use filmapik_api::scraper::{FilmapikScraper, ProviderRegistry};
+use filmapik_api::{api, config, request_id};
+use serde_json::Value;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LISTING_HTML: &str = include_str!("fixtures/listing.html");
+const DETAIL_HTML: &str = include_str!("fixtures/detail.html");
+
+const MOVIE_ID: &str = "paayum-oli-nee-yenakku-2023";
+const ORIGINAL_STREAM_URL: &str = "https://filmdewasa.org/embed/paayum-oli-nee-yenakku";
+
+macro_rules! init_app {
+    () => {{
+        let scraper = FilmapikScraper::new();
+        let providers = ProviderRegistry::from_config(&scraper).unwrap();
+        test::init_service(
+            App::new()
+                .wrap(middleware::from_fn(request_id::middleware))
+                .app_data(web::Data::new(scraper))
+                .app_data(web::Data::new(providers))
+                .configure(api::configure),
+        )
+        .await
+    }};
+}
+
+macro_rules! get {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::call_service(&$app, test::TestRequest::get().uri($uri).to_request()).await;
+        let status = resp.status();
+        (status, test::read_body(resp).await)
+    }};
+}
+
+fn html(body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body.to_string(), "text/html; charset=UTF-8")
+}
+
+#[actix_web::test]
+async fn record_then_replay_without_network() {
+    std::env::set_var("SNAPSHOT_MAX_BYTES", "0");
+    let cassette_dir = std::env::temp_dir().join(format!("filmapik-cassettes-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cassette_dir);
+
+    let server = MockServer::start().await;
+    let stream_url = format!("{}/embed/paayum-oli-nee-yenakku", server.uri());
+    for (url_path, body) in [
+        ("/".to_string(), LISTING_HTML.to_string()),
+        (format!("/{}/", MOVIE_ID), DETAIL_HTML.replace(ORIGINAL_STREAM_URL, &stream_url)),
+        ("/embed/paayum-oli-nee-yenakku".to_string(), "<html><body>player</body></html>".to_string()),
+    ] {
+        Mock::given(method("GET")).and(path(url_path)).respond_with(html(&body)).mount(&server).await;
+    }
+    config::set_filmapik_url(&server.uri());
+
+    let uris = [
+        "/api/movie/latest".to_string(),
+        format!("/api/movie/{}", MOVIE_ID),
+        format!("/api/movie/{}/watch/proxy", MOVIE_ID),
+    ];
+
+    // Rekam response dari upstream
+    vcr::set(Mode::Record, &cassette_dir);
+    let app = init_app!();
+    let mut recorded = Vec::new();
+    for uri in &uris {
+        let (status, body) = get!(app, uri);
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        recorded.push(body);
+    }
+    let upstream_requests = server.received_requests().await.unwrap().len();
+    assert_eq!(upstream_requests, 3);
+    assert_eq!(std::fs::read_dir(&cassette_dir).unwrap().count(), 3);
+
+    // Putar ulang dengan scraper (dan cache) baru: hasil harus sama dan upstream tidak dihubungi
+    vcr::set(Mode::Replay, &cassette_dir);
+    let app = init_app!();
+    for (uri, expected) in uris.iter().zip(&recorded) {
+        let (status, body) = get!(app, uri);
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(&body, expected, "{}", uri);
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), upstream_requests);
+
+    // Request tanpa cassette menghasilkan error, bukan request ke jaringan
+    let (status, body) = get!(app, "/api/movie/latest?page=2");
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "INTERNAL_ERROR");
+    assert!(body["message"].as_str().unwrap().contains("cassette"));
+    assert_eq!(server.received_requests().await.unwrap().len(), upstream_requests);
+
+    std::fs::remove_dir_all(&cassette_dir).unwrap();
+}
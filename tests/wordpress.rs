@@ -0,0 +1,163 @@
+// Test sumber data WordPress (REST API, sitemap, lalu fallback HTML) terhadap mock upstream
+use filmapik_api::scraper::wordpress::{self, CatalogSource, Taxonomy};
+use filmapik_api::scraper::{FilmapikScraper, Site};
+use std::sync::Once;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LISTING_HTML: &str = include_str!("fixtures/listing.html");
+
+static ENV: Once = Once::new();
+
+fn json(body: &str, total: u32, total_pages: u32) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .set_body_raw(body.to_string(), "application/json; charset=UTF-8")
+        .insert_header("x-wp-total", total.to_string().as_str())
+        .insert_header("x-wp-totalpages", total_pages.to_string().as_str())
+}
+
+fn xml(body: String) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body, "application/xml; charset=UTF-8")
+}
+
+async fn mount(server: &MockServer, url_path: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(url_path))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+// Setiap test memakai mock server dan scraper sendiri dengan mode WordPress
+async fn setup() -> (MockServer, FilmapikScraper) {
+    ENV.call_once(|| {
+        std::env::set_var("SNAPSHOT_MAX_BYTES", "0");
+        wordpress::set(CatalogSource::Wordpress);
+    });
+
+    let server = MockServer::start().await;
+    let scraper = FilmapikScraper::new().with_site(Site {
+        id: "wp".to_string(),
+        name: "WordPress".to_string(),
+        base_url: Some(server.uri()),
+    });
+    (server, scraper)
+}
+
+#[tokio::test]
+async fn terms_and_catalog_come_from_rest_api() {
+    let (server, scraper) = setup().await;
+
+    for (page, body) in [
+        ("1", r#"[{"slug":"action","name":"Action","count":1520}]"#),
+        ("2", r#"[{"slug":"sci-fi-fantasy","name":"Sci-Fi &amp; Fantasy","count":87}]"#),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/wp-json/wp/v2/categories"))
+            .and(query_param("page", page))
+            .respond_with(json(body, 2, 2))
+            .mount(&server)
+            .await;
+    }
+    mount(&server, "/wp-json/wp/v2/muviyear", json(r#"[{"slug":"2023","name":"2023","count":640}]"#, 1, 1)).await;
+    mount(
+        &server,
+        "/wp-json/wp/v2/posts",
+        json(
+            r#"[{"slug":"the-matrix-1999","title":{"rendered":"The Matrix (1999)"},"modified_gmt":"2024-05-01T10:00:00"}]"#,
+            8123,
+            82,
+        ),
+    )
+    .await;
+
+    let genres = scraper.get_genres().await.unwrap();
+    assert_eq!(genres.len(), 2);
+    assert_eq!(genres[0].count, Some(1520));
+    assert_eq!(genres[1].name, "Sci-Fi & Fantasy");
+    assert_eq!(genres[1].url, format!("{}/genre/sci-fi-fantasy/", server.uri()));
+
+    let years = scraper.get_terms(Taxonomy::Year).await.unwrap();
+    assert_eq!(years[0].id, "2023");
+    assert_eq!(years[0].url, format!("{}/year/2023/", server.uri()));
+
+    let catalog = scraper.get_catalog(1).await.unwrap();
+    assert_eq!(catalog.source, "rest");
+    assert_eq!(catalog.total_items, Some(8123));
+    assert_eq!(catalog.total_pages, Some(82));
+    assert_eq!(catalog.entries[0].id, "the-matrix-1999");
+    assert_eq!(catalog.entries[0].title.as_deref(), Some("The Matrix (1999)"));
+    assert_eq!(catalog.entries[0].provider, "wp");
+}
+
+#[tokio::test]
+async fn sitemaps_are_used_when_rest_api_is_disabled() {
+    let (server, scraper) = setup().await;
+    let uri = server.uri();
+
+    // REST API diblokir plugin keamanan
+    Mock::given(method("GET"))
+        .and(wiremock::matchers::path_regex("^/wp-json/"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    mount(&server, "/wp-sitemap.xml", ResponseTemplate::new(404)).await;
+    mount(
+        &server,
+        "/sitemap_index.xml",
+        xml(format!(
+            "<sitemapindex><sitemap><loc>{uri}/post-sitemap.xml</loc></sitemap>\
+             <sitemap><loc>{uri}/post-sitemap2.xml</loc></sitemap>\
+             <sitemap><loc>{uri}/muvicountry-sitemap.xml</loc></sitemap></sitemapindex>"
+        )),
+    )
+    .await;
+    let posts: String = (1..=150)
+        .map(|i| format!("<url><loc>{uri}/film-{i}/</loc><lastmod>2024-01-01T00:00:00+00:00</lastmod></url>"))
+        .collect();
+    mount(&server, "/post-sitemap.xml", xml(format!("<urlset>{posts}</urlset>"))).await;
+    mount(&server, "/post-sitemap2.xml", xml(format!("<urlset><url><loc>{uri}/film-151/</loc></url></urlset>"))).await;
+    mount(
+        &server,
+        "/muvicountry-sitemap.xml",
+        xml(format!("<urlset><url><loc>{uri}/country/hong-kong/</loc></url></urlset>")),
+    )
+    .await;
+
+    let countries = scraper.get_countries().await.unwrap();
+    assert_eq!(countries.len(), 1);
+    assert_eq!(countries[0].id, "hong-kong");
+    assert_eq!(countries[0].name, "Hong Kong");
+    assert_eq!(countries[0].count, None);
+
+    let catalog = scraper.get_catalog(2).await.unwrap();
+    assert_eq!(catalog.source, "sitemap");
+    assert_eq!(catalog.total_items, Some(151));
+    assert_eq!(catalog.total_pages, Some(2));
+    assert_eq!(catalog.entries.len(), 51);
+    assert_eq!(catalog.entries[0].id, "film-101");
+    assert_eq!(catalog.entries[0].title, None);
+    assert_eq!(catalog.entries.last().unwrap().modified, None);
+}
+
+#[tokio::test]
+async fn falls_back_to_html_without_wordpress_endpoints() {
+    let (server, scraper) = setup().await;
+    mount(&server, "/", ResponseTemplate::new(200).set_body_raw(LISTING_HTML, "text/html; charset=UTF-8")).await;
+
+    let genres = scraper.get_genres().await.unwrap();
+    assert!(genres.iter().any(|g| g.id == "action"));
+    assert!(genres.iter().all(|g| g.count.is_none()));
+
+    // Sutradara tidak memiliki daftar statis, tahun dibentuk dari rentang tahun
+    assert!(scraper.get_terms(Taxonomy::Director).await.unwrap().is_empty());
+    let years = scraper.get_terms(Taxonomy::Year).await.unwrap();
+    assert_eq!(years.last().unwrap().id, "1950");
+
+    let catalog = scraper.get_catalog(1).await.unwrap();
+    assert_eq!(catalog.source, "html");
+    assert_eq!(catalog.total_items, None);
+    assert!(!catalog.entries.is_empty());
+    assert!(catalog.entries.iter().all(|e| e.title.is_some()));
+}